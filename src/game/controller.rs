@@ -1,6 +1,19 @@
 use bevy::prelude::*;
 
-use super::model::GameState;
+use super::{
+    model::{GameState, SimpleCamera, Suspicion},
+    CAMERA_SMOOTHING,
+};
+use crate::{
+    host::model::MonitoringAlert,
+    input::model::HasInput,
+    interaction::model::InteractionEvent,
+    object::{
+        level::Level,
+        model::{CurrentLevel, Terminal},
+    },
+    shell::model::{EvidenceEvent, Hostname, Selected, ShellData},
+};
 
 pub fn unpause(keys: Res<Input<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
     if keys.just_pressed(KeyCode::Escape) {
@@ -15,13 +28,94 @@ pub fn pause(keys: Res<Input<KeyCode>>, mut next_state: ResMut<NextState<GameSta
 }
 
 pub fn exit_terminal(keys: Res<Input<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
-    if keys.pressed(KeyCode::LShift) && keys.just_pressed(KeyCode::T) {
+    // Ctrl+D ends the session like it would in a real shell, the line editor has no use for it
+    if keys.any_pressed([KeyCode::LControl, KeyCode::RControl]) && keys.just_pressed(KeyCode::D) {
         next_state.set(GameState::Movement);
     }
 }
 
-pub fn enter_terminal(keys: Res<Input<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
-    if keys.pressed(KeyCode::LShift) && keys.just_pressed(KeyCode::T) {
+pub fn enter_terminal(
+    mut commands: Commands,
+    mut interactions: EventReader<InteractionEvent>,
+    terminals: Query<&Terminal>,
+    shells: Query<(Entity, &Hostname), With<ShellData>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for event in interactions.iter() {
+        let Ok(terminal) = terminals.get(event.target) else {
+            continue;
+        };
+
+        let Some((entity, _)) = shells
+            .iter()
+            .find(|(_, hostname)| ***hostname == terminal.hostname)
+        else {
+            warn!("terminal for unknown host '{}'", terminal.hostname);
+            continue;
+        };
+
+        commands.entity(entity).insert(Selected);
         next_state.set(GameState::Shell);
     }
 }
+
+pub fn follow_player(
+    time: Res<Time>,
+    levels: Res<Assets<Level>>,
+    current_level: Res<CurrentLevel>,
+    players: Query<&Transform, (With<HasInput>, Without<SimpleCamera>)>,
+    mut cameras: Query<(&mut Transform, &OrthographicProjection), With<SimpleCamera>>,
+    mut placed: Local<bool>,
+) {
+    let Ok(player) = players.get_single() else {
+        return;
+    };
+
+    let smoothing = match *placed {
+        true => 1. - (-CAMERA_SMOOTHING * time.delta_seconds()).exp(),
+        false => 1.,
+    };
+
+    *placed = true;
+
+    for (mut transform, projection) in cameras.iter_mut() {
+        let mut target = player.translation.truncate();
+
+        if let Some(bounds) = levels.get(&current_level).map(Level::bounds) {
+            let slack = (bounds.half_size() - projection.area.half_size()).max(Vec2::ZERO);
+
+            target = target.clamp(bounds.center() - slack, bounds.center() + slack);
+        }
+
+        let position = transform.translation.truncate().lerp(target, smoothing);
+
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
+
+pub fn monitoring_alerts(
+    mut alerts: EventReader<MonitoringAlert>,
+    mut suspicion: ResMut<Suspicion>,
+) {
+    for event in alerts.iter() {
+        info!(
+            "monitoring alert on {}: {} ({})",
+            event.hostname, event.alert.rule, event.alert.line
+        );
+
+        **suspicion += event.alert.rule.severity();
+    }
+}
+
+pub fn raise_suspicion(
+    mut evidence_events: EventReader<EvidenceEvent>,
+    mut suspicion: ResMut<Suspicion>,
+) {
+    for event in evidence_events.iter() {
+        for evidence in &event.evidence {
+            info!("evidence left on {}: {evidence}", event.hostname);
+        }
+
+        **suspicion += event.evidence.len() as f32;
+    }
+}
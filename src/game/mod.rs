@@ -1,9 +1,18 @@
 use self::{
-    controller::{enter_terminal, exit_terminal, pause, unpause},
-    model::{GameState, SimpleCamera},
+    controller::{
+        enter_terminal, exit_terminal, follow_player, monitoring_alerts, pause, raise_suspicion,
+        unpause,
+    },
+    model::{GameState, SimpleCamera, Suspicion},
 };
 use crate::{
-    input::InputPlugin, object::ObjectsPlugin, physics::PhysicsPlugin, shell::ShellPlugin,
+    host::HostPlugin,
+    input::InputPlugin,
+    interaction::InteractionPlugin,
+    item::ItemPlugin,
+    object::ObjectsPlugin,
+    physics::PhysicsPlugin,
+    shell::ShellPlugin,
 };
 use bevy::{
     diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
@@ -17,6 +26,7 @@ pub mod view;
 
 pub const WINDOW_WIDTH: f32 = 960.;
 pub const WINDOW_HEIGHT: f32 = 540.;
+const CAMERA_SMOOTHING: f32 = 6.;
 
 pub struct Game;
 
@@ -32,21 +42,29 @@ impl Plugin for Game {
                     }),
                     ..default()
                 })
-                .set(ImagePlugin::default_nearest()),
+                .set(ImagePlugin::default_nearest())
+                .set(AssetPlugin {
+                    watch_for_changes: cfg!(debug_assertions),
+                    ..default()
+                }),
         )
         .add_state::<GameState>()
+        .init_resource::<Suspicion>()
         .add_plugin(LogDiagnosticsPlugin::default())
         .add_plugin(FrameTimeDiagnosticsPlugin::default())
         .add_plugin(InputPlugin)
+        .add_plugin(InteractionPlugin)
+        .add_plugin(HostPlugin)
         .add_plugin(ShellPlugin)
+        .add_plugin(ItemPlugin)
         .add_plugin(PhysicsPlugin)
         .add_plugin(ObjectsPlugin)
         .add_startup_system(setup)
-        .add_system(pause.in_set(OnUpdate(GameState::Shell)))
         .add_system(pause.in_set(OnUpdate(GameState::Movement)))
         .add_system(unpause.in_set(OnUpdate(GameState::Paused)))
         .add_system(exit_terminal.in_set(OnUpdate(GameState::Shell)))
-        .add_system(enter_terminal.in_set(OnUpdate(GameState::Movement)));
+        .add_system(enter_terminal.in_set(OnUpdate(GameState::Movement)))
+        .add_systems((raise_suspicion, monitoring_alerts, follow_player));
     }
 }
 
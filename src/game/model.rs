@@ -10,3 +10,6 @@ pub enum GameState {
     #[default]
     Movement,
 }
+
+#[derive(Resource, Default, Debug, Deref, DerefMut)]
+pub struct Suspicion(pub f32);
@@ -0,0 +1,123 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+
+use super::{
+    model::{DatabaseEvent, DeploymentEvent, Host, MonitoringAlert, ServiceEvent},
+    monitoring::LogFile,
+    process::ServiceState,
+};
+use crate::shell::model::Hostname;
+
+pub fn tick_hosts(
+    time: Res<Time>,
+    mut hosts: Query<(&Hostname, &mut Host)>,
+    mut deployment_events: EventWriter<DeploymentEvent>,
+) {
+    let schema_versions = hosts
+        .iter()
+        .filter_map(|(hostname, host)| {
+            let database = host.state.database.as_ref()?;
+
+            Some((hostname.to_string(), database.schema_version))
+        })
+        .collect::<HashMap<_, _>>();
+
+    for (hostname, mut host) in hosts.iter_mut() {
+        if let Some(outcome) = host.state.tick(time.delta_seconds(), &schema_versions) {
+            deployment_events.send(DeploymentEvent {
+                hostname: hostname.to_string(),
+                version: host.state.version.clone(),
+                outcome,
+            });
+        }
+    }
+}
+
+pub fn tick_databases(
+    time: Res<Time>,
+    mut hosts: Query<(&Hostname, &mut Host)>,
+    mut database_events: EventWriter<DatabaseEvent>,
+) {
+    for (hostname, mut host) in hosts.iter_mut() {
+        let Some(database) = &mut host.state.database else {
+            continue;
+        };
+
+        for event in database.tick(time.delta_seconds()) {
+            database_events.send(DatabaseEvent {
+                hostname: hostname.to_string(),
+                event,
+            });
+        }
+    }
+}
+
+pub fn tick_services(
+    mut hosts: Query<(&Hostname, &mut Host)>,
+    mut service_events: EventWriter<ServiceEvent>,
+) {
+    let active = hosts
+        .iter()
+        .flat_map(|(hostname, host)| host.state.processes.active(hostname).collect::<Vec<_>>())
+        .collect::<HashSet<_>>();
+
+    for (hostname, mut host) in hosts.iter_mut() {
+        host.state.processes.fail_unmet(hostname, &active);
+
+        for (service, state) in host.state.processes.drain_changes() {
+            service_events.send(ServiceEvent {
+                hostname: hostname.to_string(),
+                service,
+                state,
+            });
+        }
+    }
+}
+
+pub fn write_logs(
+    mut hosts: Query<(&Hostname, &mut Host)>,
+    mut deployment_events: EventReader<DeploymentEvent>,
+    mut database_events: EventReader<DatabaseEvent>,
+    mut service_events: EventReader<ServiceEvent>,
+) {
+    let deployments = deployment_events.iter().map(|event| {
+        (
+            &event.hostname,
+            format!("deployed version {}: {}", event.version, event.outcome),
+        )
+    });
+    let migrations = database_events
+        .iter()
+        .map(|event| (&event.hostname, event.event.to_string()));
+    let services = service_events.iter().map(|event| {
+        let message = match event.state {
+            ServiceState::Active => format!("{} started", event.service),
+            ServiceState::Inactive => format!("{} stopped", event.service),
+            ServiceState::Failed => format!("{} entered failed state", event.service),
+        };
+
+        (&event.hostname, message)
+    });
+
+    for (hostname, message) in deployments.chain(migrations).chain(services) {
+        if let Some((_, mut host)) = hosts.iter_mut().find(|(name, _)| ***name == *hostname) {
+            host.log(LogFile::Syslog, &message);
+        }
+    }
+}
+
+pub fn monitor_hosts(
+    time: Res<Time>,
+    mut hosts: Query<(&Hostname, &mut Host)>,
+    mut alerts: EventWriter<MonitoringAlert>,
+) {
+    for (hostname, mut host) in hosts.iter_mut() {
+        for alert in host.monitor(time.delta_seconds()) {
+            alerts.send(MonitoringAlert {
+                hostname: hostname.to_string(),
+                alert,
+            });
+        }
+    }
+}
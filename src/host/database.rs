@@ -0,0 +1,508 @@
+use std::collections::BTreeMap;
+
+const ROWS_PER_SECOND: f32 = 20_000.;
+const STEP_SECONDS: f32 = 1.;
+const LOCK_ALARM_SECONDS: f32 = 20.;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Step {
+    CreateTable(String),
+    DropTable(String),
+    CopyRows { from: String, to: String },
+    Truncate(String),
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::CreateTable(table) => write!(f, "create table {table}"),
+            Step::DropTable(table) => write!(f, "drop table {table}"),
+            Step::CopyRows { from, to } => write!(f, "copy rows from {from} to {to}"),
+            Step::Truncate(table) => write!(f, "truncate {table}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Migration {
+    pub version: u32,
+    pub name: String,
+    pub up: Vec<Step>,
+    pub down: Vec<Step>,
+    pub fails_at: Option<usize>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::Up => write!(f, "up"),
+            Direction::Down => write!(f, "down"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RunningMigration {
+    pub version: u32,
+    pub direction: Direction,
+    pub steps: Vec<Step>,
+    pub step: usize,
+    fails_at: Option<usize>,
+    elapsed: f32,
+}
+
+impl std::fmt::Display for RunningMigration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "migration {} {}, step {}/{}",
+            self.version,
+            self.direction,
+            self.step + 1,
+            self.steps.len()
+        )?;
+
+        match self.steps.get(self.step) {
+            Some(step) => write!(f, " ({step})"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DatabaseError {
+    NoDatabase,
+    Locked(String),
+    UpToDate(u32),
+    UnknownMigration(u32),
+    NotLocked,
+}
+
+impl std::fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DatabaseError::NoDatabase => write!(f, "no database on this host"),
+            DatabaseError::Locked(holder) => write!(f, "database is locked by {holder}"),
+            DatabaseError::UpToDate(version) => {
+                write!(f, "schema is already at version {version}")
+            }
+            DatabaseError::UnknownMigration(version) => write!(f, "no migration {version}"),
+            DatabaseError::NotLocked => write!(f, "database is not locked"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MigrationEvent {
+    Completed(u32, Direction),
+    Failed(u32, Step),
+    LockHeld(f32),
+}
+
+impl std::fmt::Display for MigrationEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationEvent::Completed(version, direction) => {
+                write!(f, "migration {version} {direction} completed")
+            }
+            MigrationEvent::Failed(version, step) => {
+                write!(f, "migration {version} failed at '{step}'")
+            }
+            MigrationEvent::LockHeld(seconds) => {
+                write!(f, "database locked for {seconds:.0} seconds")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Database {
+    pub tables: BTreeMap<String, u64>,
+    pub schema_version: u32,
+    pub migrations: Vec<Migration>,
+    pub running: Option<RunningMigration>,
+    pub lock: Option<String>,
+    lock_held: f32,
+    target: u32,
+}
+
+impl Database {
+    pub fn new(schema_version: u32) -> Self {
+        Self {
+            schema_version,
+            target: schema_version,
+            ..Default::default()
+        }
+    }
+
+    pub fn latest_version(&self) -> u32 {
+        self.migrations
+            .iter()
+            .map(|migration| migration.version)
+            .max()
+            .unwrap_or_default()
+            .max(self.schema_version)
+    }
+
+    pub fn pending(&self) -> usize {
+        self.migrations
+            .iter()
+            .filter(|migration| migration.version > self.schema_version)
+            .count()
+    }
+
+    pub fn migrate(&mut self, target: Option<u32>) -> Result<&RunningMigration, DatabaseError> {
+        self.check_lock()?;
+
+        let target = target.unwrap_or(self.latest_version());
+
+        if target <= self.schema_version {
+            return Err(DatabaseError::UpToDate(self.schema_version));
+        }
+
+        self.start(self.schema_version + 1, Direction::Up, target)
+    }
+
+    pub fn rollback(&mut self) -> Result<&RunningMigration, DatabaseError> {
+        self.check_lock()?;
+
+        self.start(
+            self.schema_version,
+            Direction::Down,
+            self.schema_version.saturating_sub(1),
+        )
+    }
+
+    pub fn unlock(&mut self) -> Result<(), DatabaseError> {
+        if let Some(running) = &self.running {
+            return Err(DatabaseError::Locked(format!(
+                "running migration {}",
+                running.version
+            )));
+        }
+
+        self.lock.take().ok_or(DatabaseError::NotLocked)?;
+        self.lock_held = 0.;
+
+        Ok(())
+    }
+
+    pub fn truncate_all(&mut self) {
+        for rows in self.tables.values_mut() {
+            *rows = 0;
+        }
+    }
+
+    fn check_lock(&self) -> Result<(), DatabaseError> {
+        match &self.lock {
+            Some(holder) => Err(DatabaseError::Locked(holder.clone())),
+            None => Ok(()),
+        }
+    }
+
+    fn start(
+        &mut self,
+        version: u32,
+        direction: Direction,
+        target: u32,
+    ) -> Result<&RunningMigration, DatabaseError> {
+        let migration = self
+            .migrations
+            .iter()
+            .find(|migration| migration.version == version)
+            .ok_or(DatabaseError::UnknownMigration(version))?;
+
+        let (steps, fails_at) = match direction {
+            Direction::Up => (migration.up.clone(), migration.fails_at),
+            Direction::Down => (migration.down.clone(), None),
+        };
+
+        self.target = target;
+        self.lock = Some(format!("migration {version}"));
+
+        Ok(self.running.insert(RunningMigration {
+            version,
+            direction,
+            steps,
+            step: 0,
+            fails_at,
+            elapsed: 0.,
+        }))
+    }
+
+    fn duration(&self, step: &Step) -> f32 {
+        match step {
+            Step::CopyRows { from, .. } => {
+                let rows = self.tables.get(from).copied().unwrap_or_default();
+                STEP_SECONDS.max(rows as f32 / ROWS_PER_SECOND)
+            }
+            _ => STEP_SECONDS,
+        }
+    }
+
+    fn apply(&mut self, step: &Step) {
+        match step {
+            Step::CreateTable(table) => {
+                self.tables.entry(table.clone()).or_default();
+            }
+            Step::DropTable(table) => {
+                self.tables.remove(table);
+            }
+            Step::CopyRows { from, to } => {
+                let rows = self.tables.get(from).copied().unwrap_or_default();
+                *self.tables.entry(to.clone()).or_default() += rows;
+            }
+            Step::Truncate(table) => {
+                if let Some(rows) = self.tables.get_mut(table) {
+                    *rows = 0;
+                }
+            }
+        }
+    }
+
+    pub fn tick(&mut self, delta: f32) -> Vec<MigrationEvent> {
+        let mut events = Vec::new();
+
+        if self.lock.is_some() {
+            let held = self.lock_held;
+            self.lock_held += delta;
+
+            if held < LOCK_ALARM_SECONDS && self.lock_held >= LOCK_ALARM_SECONDS {
+                events.push(MigrationEvent::LockHeld(self.lock_held));
+            }
+        }
+
+        let Some(mut running) = self.running.take() else {
+            return events;
+        };
+
+        running.elapsed += delta;
+
+        while let Some(step) = running.steps.get(running.step).cloned() {
+            let duration = self.duration(&step);
+
+            if running.elapsed < duration {
+                self.running = Some(running);
+                return events;
+            }
+
+            if running.fails_at == Some(running.step) {
+                events.push(MigrationEvent::Failed(running.version, step));
+                return events;
+            }
+
+            running.elapsed -= duration;
+            running.step += 1;
+            self.apply(&step);
+        }
+
+        self.schema_version = match running.direction {
+            Direction::Up => running.version,
+            Direction::Down => running.version - 1,
+        };
+
+        events.push(MigrationEvent::Completed(
+            running.version,
+            running.direction,
+        ));
+
+        let continued = running.direction == Direction::Up
+            && self.target > self.schema_version
+            && self
+                .start(self.schema_version + 1, Direction::Up, self.target)
+                .is_ok();
+
+        if !continued {
+            self.lock = None;
+            self.lock_held = 0.;
+        }
+
+        events
+    }
+
+    pub fn status(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "schema version: {} ({} pending)",
+            self.schema_version,
+            self.pending()
+        )];
+
+        lines.push(match &self.lock {
+            Some(holder) => format!("lock: held by {holder} for {:.0}s", self.lock_held),
+            None => String::from("lock: free"),
+        });
+
+        if let Some(running) = &self.running {
+            lines.push(format!("running: {running}"));
+        }
+
+        lines.extend(
+            self.migrations
+                .iter()
+                .filter(|migration| migration.version > self.schema_version)
+                .map(|migration| format!("pending: {} {}", migration.version, migration.name)),
+        );
+
+        lines.extend(
+            self.tables
+                .iter()
+                .map(|(table, rows)| format!("  {table:<16}{rows:>10} rows")),
+        );
+
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migration(version: u32, table: &str, fails_at: Option<usize>) -> Migration {
+        Migration {
+            version,
+            name: format!("create {table}"),
+            up: vec![Step::CreateTable(table.to_string())],
+            down: vec![Step::DropTable(table.to_string())],
+            fails_at,
+        }
+    }
+
+    fn database() -> Database {
+        let mut database = Database::new(1);
+
+        database.migrations = vec![migration(2, "accounts", None), migration(3, "orders", None)];
+
+        database
+    }
+
+    #[test]
+    fn migrate_steps_through_every_pending_migration() {
+        let mut database = database();
+
+        database.migrate(None).unwrap();
+
+        assert_eq!(database.tick(STEP_SECONDS / 2.), vec![]);
+        assert_eq!(
+            database.tick(STEP_SECONDS / 2.),
+            vec![MigrationEvent::Completed(2, Direction::Up)]
+        );
+        assert!(database.lock.is_some());
+        assert_eq!(
+            database.tick(STEP_SECONDS),
+            vec![MigrationEvent::Completed(3, Direction::Up)]
+        );
+        assert_eq!(database.schema_version, 3);
+        assert_eq!(database.pending(), 0);
+        assert!(database.lock.is_none());
+        assert!(database.tables.contains_key("accounts"));
+        assert!(database.tables.contains_key("orders"));
+    }
+
+    #[test]
+    fn migrate_stops_at_the_target() {
+        let mut database = database();
+
+        database.migrate(Some(2)).unwrap();
+        database.tick(STEP_SECONDS);
+
+        assert_eq!(database.schema_version, 2);
+        assert!(database.running.is_none());
+        assert_eq!(
+            database.migrate(Some(2)).err(),
+            Some(DatabaseError::UpToDate(2))
+        );
+    }
+
+    #[test]
+    fn rollback_reverts_the_last_migration() {
+        let mut database = database();
+
+        database.migrate(Some(2)).unwrap();
+        database.tick(STEP_SECONDS);
+        database.rollback().unwrap();
+
+        assert_eq!(
+            database.tick(STEP_SECONDS),
+            vec![MigrationEvent::Completed(2, Direction::Down)]
+        );
+        assert_eq!(database.schema_version, 1);
+        assert!(!database.tables.contains_key("accounts"));
+    }
+
+    #[test]
+    fn unknown_migration_leaves_the_database_unlocked() {
+        let mut database = database();
+
+        assert_eq!(
+            database.rollback().err(),
+            Some(DatabaseError::UnknownMigration(1))
+        );
+        assert!(database.lock.is_none());
+
+        database.migrate(Some(2)).unwrap();
+        database.tick(STEP_SECONDS);
+
+        assert_eq!(database.schema_version, 2);
+        assert!(database.running.is_none());
+    }
+
+    #[test]
+    fn failed_migration_holds_the_lock() {
+        let mut database = Database::new(1);
+
+        database.migrations = vec![migration(2, "accounts", Some(0))];
+        database.migrate(None).unwrap();
+
+        assert_eq!(
+            database.tick(STEP_SECONDS),
+            vec![MigrationEvent::Failed(
+                2,
+                Step::CreateTable(String::from("accounts"))
+            )]
+        );
+        assert_eq!(database.schema_version, 1);
+        assert_eq!(
+            database.migrate(None).err(),
+            Some(DatabaseError::Locked(String::from("migration 2")))
+        );
+        assert_eq!(
+            database.tick(LOCK_ALARM_SECONDS),
+            vec![MigrationEvent::LockHeld(LOCK_ALARM_SECONDS + STEP_SECONDS)]
+        );
+
+        database.unlock().unwrap();
+
+        assert_eq!(database.unlock(), Err(DatabaseError::NotLocked));
+    }
+
+    #[test]
+    fn copying_rows_takes_longer_for_large_tables() {
+        let mut database = Database::new(1);
+
+        database
+            .tables
+            .insert(String::from("orders"), (ROWS_PER_SECOND * 3.) as u64);
+        database.migrations = vec![Migration {
+            version: 2,
+            up: vec![Step::CopyRows {
+                from: String::from("orders"),
+                to: String::from("archive"),
+            }],
+            ..Default::default()
+        }];
+        database.migrate(None).unwrap();
+
+        assert_eq!(database.tick(2. * STEP_SECONDS), vec![]);
+        assert_eq!(
+            database.tick(STEP_SECONDS),
+            vec![MigrationEvent::Completed(2, Direction::Up)]
+        );
+        assert_eq!(database.tables["archive"], database.tables["orders"]);
+    }
+}
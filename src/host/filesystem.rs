@@ -0,0 +1,675 @@
+use std::collections::BTreeMap;
+
+use crate::shell::model::AccessLevel;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileSystemError {
+    NotFound,
+    PermissionDenied,
+    NotADirectory,
+    IsADirectory,
+    AlreadyExists,
+    DirectoryNotEmpty,
+}
+
+impl std::fmt::Display for FileSystemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileSystemError::NotFound => write!(f, "no such file or directory"),
+            FileSystemError::PermissionDenied => write!(f, "permission denied"),
+            FileSystemError::NotADirectory => write!(f, "not a directory"),
+            FileSystemError::IsADirectory => write!(f, "is a directory"),
+            FileSystemError::AlreadyExists => write!(f, "file exists"),
+            FileSystemError::DirectoryNotEmpty => write!(f, "directory not empty"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Permissions {
+    pub read: AccessLevel,
+    pub write: AccessLevel,
+}
+
+impl Permissions {
+    pub fn new(read: AccessLevel, write: AccessLevel) -> Self {
+        Self { read, write }
+    }
+
+    pub fn restricted(access_level: AccessLevel) -> Self {
+        Self::new(access_level, access_level)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Node {
+    File {
+        contents: String,
+        permissions: Permissions,
+    },
+    Directory {
+        entries: BTreeMap<String, Node>,
+        permissions: Permissions,
+    },
+}
+
+impl Node {
+    fn directory(permissions: Permissions) -> Self {
+        Node::Directory {
+            entries: BTreeMap::new(),
+            permissions,
+        }
+    }
+
+    fn permissions(&self) -> Permissions {
+        match self {
+            Node::File { permissions, .. } | Node::Directory { permissions, .. } => *permissions,
+        }
+    }
+
+    fn can_read(&self, access_level: AccessLevel) -> bool {
+        access_level >= self.permissions().read
+    }
+
+    fn can_write(&self, access_level: AccessLevel) -> bool {
+        access_level >= self.permissions().write
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FileSystem {
+    root: Node,
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        let mut filesystem = Self {
+            root: Node::directory(Permissions::new(AccessLevel::Guest, AccessLevel::Root)),
+        };
+
+        for (path, permissions) in [
+            (
+                "/etc",
+                Permissions::new(AccessLevel::Guest, AccessLevel::Operator),
+            ),
+            ("/home", Permissions::default()),
+            ("/root", Permissions::restricted(AccessLevel::Root)),
+            ("/tmp", Permissions::default()),
+            (
+                "/var",
+                Permissions::new(AccessLevel::Guest, AccessLevel::Operator),
+            ),
+        ] {
+            filesystem
+                .create_directory("/", path, permissions)
+                .expect("default directories are valid");
+        }
+
+        filesystem
+    }
+}
+
+impl FileSystem {
+    pub fn resolve(cwd: &str, path: &str) -> Vec<String> {
+        let mut components = Vec::new();
+
+        let path = if path.starts_with('/') {
+            path.to_string()
+        } else {
+            format!("{cwd}/{path}")
+        };
+
+        for component in path.split('/') {
+            match component {
+                "" | "." => (),
+                ".." => {
+                    components.pop();
+                }
+                component => components.push(component.to_string()),
+            }
+        }
+
+        components
+    }
+
+    pub fn display(components: &[String]) -> String {
+        format!("/{}", components.join("/"))
+    }
+
+    fn node(
+        &self,
+        components: &[String],
+        access_level: AccessLevel,
+    ) -> Result<&Node, FileSystemError> {
+        let mut node = &self.root;
+
+        for component in components {
+            node = match node {
+                Node::Directory { entries, .. } if node.can_read(access_level) => {
+                    entries.get(component).ok_or(FileSystemError::NotFound)?
+                }
+                Node::Directory { .. } => return Err(FileSystemError::PermissionDenied),
+                Node::File { .. } => return Err(FileSystemError::NotADirectory),
+            };
+        }
+
+        Ok(node)
+    }
+
+    fn directory_mut(
+        &mut self,
+        components: &[String],
+        access_level: AccessLevel,
+    ) -> Result<&mut BTreeMap<String, Node>, FileSystemError> {
+        let mut node = &mut self.root;
+
+        for component in components {
+            if !node.can_read(access_level) {
+                return Err(FileSystemError::PermissionDenied);
+            }
+
+            node = match node {
+                Node::Directory { entries, .. } => entries
+                    .get_mut(component)
+                    .ok_or(FileSystemError::NotFound)?,
+                Node::File { .. } => return Err(FileSystemError::NotADirectory),
+            };
+        }
+
+        if !node.can_write(access_level) {
+            return Err(FileSystemError::PermissionDenied);
+        }
+
+        match node {
+            Node::Directory { entries, .. } => Ok(entries),
+            Node::File { .. } => Err(FileSystemError::NotADirectory),
+        }
+    }
+
+    fn file_mut(
+        &mut self,
+        cwd: &str,
+        path: &str,
+        access_level: AccessLevel,
+    ) -> Result<&mut String, FileSystemError> {
+        let components = Self::resolve(cwd, path);
+        let (parent, name) = Self::split(&components)?;
+
+        match self.directory_mut(parent, access_level)?.get_mut(name) {
+            None => Err(FileSystemError::NotFound),
+            Some(Node::Directory { .. }) => Err(FileSystemError::IsADirectory),
+            Some(node) if !node.can_write(access_level) => Err(FileSystemError::PermissionDenied),
+            Some(Node::File { contents, .. }) => Ok(contents),
+        }
+    }
+
+    fn split(components: &[String]) -> Result<(&[String], &String), FileSystemError> {
+        match components.split_last() {
+            Some((name, parent)) => Ok((parent, name)),
+            None => Err(FileSystemError::PermissionDenied),
+        }
+    }
+
+    pub fn is_directory(&self, cwd: &str, path: &str, access_level: AccessLevel) -> bool {
+        matches!(
+            self.node(&Self::resolve(cwd, path), access_level),
+            Ok(Node::Directory { .. })
+        )
+    }
+
+    pub fn change_directory(
+        &self,
+        cwd: &str,
+        path: &str,
+        access_level: AccessLevel,
+    ) -> Result<String, FileSystemError> {
+        let components = Self::resolve(cwd, path);
+
+        match self.node(&components, access_level)? {
+            Node::Directory { .. } => Ok(Self::display(&components)),
+            Node::File { .. } => Err(FileSystemError::NotADirectory),
+        }
+    }
+
+    pub fn list(
+        &self,
+        cwd: &str,
+        path: &str,
+        access_level: AccessLevel,
+    ) -> Result<Vec<String>, FileSystemError> {
+        let components = Self::resolve(cwd, path);
+        let node = self.node(&components, access_level)?;
+
+        if !node.can_read(access_level) {
+            return Err(FileSystemError::PermissionDenied);
+        }
+
+        match node {
+            Node::Directory { entries, .. } => Ok(entries
+                .iter()
+                .map(|(name, node)| match node {
+                    Node::Directory { .. } => format!("{name}/"),
+                    Node::File { .. } => name.clone(),
+                })
+                .collect()),
+            Node::File { .. } => Ok(vec![components.last().cloned().unwrap_or_default()]),
+        }
+    }
+
+    pub fn read(
+        &self,
+        cwd: &str,
+        path: &str,
+        access_level: AccessLevel,
+    ) -> Result<&str, FileSystemError> {
+        let node = self.node(&Self::resolve(cwd, path), access_level)?;
+
+        if !node.can_read(access_level) {
+            return Err(FileSystemError::PermissionDenied);
+        }
+
+        match node {
+            Node::File { contents, .. } => Ok(contents),
+            Node::Directory { .. } => Err(FileSystemError::IsADirectory),
+        }
+    }
+
+    pub fn write(
+        &mut self,
+        cwd: &str,
+        path: &str,
+        contents: &str,
+        access_level: AccessLevel,
+    ) -> Result<(), FileSystemError> {
+        self.create_file(cwd, path, contents, Permissions::default(), access_level)
+    }
+
+    pub fn create_file(
+        &mut self,
+        cwd: &str,
+        path: &str,
+        contents: &str,
+        permissions: Permissions,
+        access_level: AccessLevel,
+    ) -> Result<(), FileSystemError> {
+        let components = Self::resolve(cwd, path);
+        let (parent, name) = Self::split(&components)?;
+        let entries = self.directory_mut(parent, access_level)?;
+
+        match entries.get_mut(name) {
+            Some(Node::Directory { .. }) => Err(FileSystemError::IsADirectory),
+            Some(node) if !node.can_write(access_level) => Err(FileSystemError::PermissionDenied),
+            Some(Node::File {
+                contents: existing, ..
+            }) => {
+                *existing = contents.to_string();
+                Ok(())
+            }
+            None => {
+                entries.insert(
+                    name.clone(),
+                    Node::File {
+                        contents: contents.to_string(),
+                        permissions,
+                    },
+                );
+                Ok(())
+            }
+        }
+    }
+
+    pub fn append(
+        &mut self,
+        cwd: &str,
+        path: &str,
+        contents: &str,
+        access_level: AccessLevel,
+    ) -> Result<(), FileSystemError> {
+        self.file_mut(cwd, path, access_level)?.push_str(contents);
+
+        Ok(())
+    }
+
+    pub fn truncate_front(
+        &mut self,
+        cwd: &str,
+        path: &str,
+        length: usize,
+        access_level: AccessLevel,
+    ) -> Result<(), FileSystemError> {
+        let contents = self.file_mut(cwd, path, access_level)?;
+
+        if contents.len() <= length {
+            return Ok(());
+        }
+
+        let start = contents.len() - length;
+
+        // Only whole lines are dropped, so the file never starts halfway through one
+        let start = match contents.as_bytes()[start..]
+            .iter()
+            .position(|byte| *byte == b'\n')
+        {
+            Some(idx) => start + idx + 1,
+            None => contents.len(),
+        };
+
+        contents.drain(..start);
+
+        Ok(())
+    }
+
+    pub fn make_directory(
+        &mut self,
+        cwd: &str,
+        path: &str,
+        access_level: AccessLevel,
+    ) -> Result<(), FileSystemError> {
+        self.create_directory_as(cwd, path, Permissions::default(), access_level)
+    }
+
+    pub fn create_directory(
+        &mut self,
+        cwd: &str,
+        path: &str,
+        permissions: Permissions,
+    ) -> Result<(), FileSystemError> {
+        let components = Self::resolve(cwd, path);
+        let mut node = &mut self.root;
+
+        for component in components {
+            node = match node {
+                Node::Directory { entries, .. } => entries
+                    .entry(component)
+                    .or_insert_with(|| Node::directory(permissions)),
+                Node::File { .. } => return Err(FileSystemError::NotADirectory),
+            };
+        }
+
+        match node {
+            Node::Directory { .. } => Ok(()),
+            Node::File { .. } => Err(FileSystemError::AlreadyExists),
+        }
+    }
+
+    fn create_directory_as(
+        &mut self,
+        cwd: &str,
+        path: &str,
+        permissions: Permissions,
+        access_level: AccessLevel,
+    ) -> Result<(), FileSystemError> {
+        let components = Self::resolve(cwd, path);
+        let (parent, name) = Self::split(&components)?;
+        let entries = self.directory_mut(parent, access_level)?;
+
+        if entries.contains_key(name) {
+            return Err(FileSystemError::AlreadyExists);
+        }
+
+        entries.insert(name.clone(), Node::directory(permissions));
+
+        Ok(())
+    }
+
+    pub fn remove(
+        &mut self,
+        cwd: &str,
+        path: &str,
+        access_level: AccessLevel,
+    ) -> Result<(), FileSystemError> {
+        let components = Self::resolve(cwd, path);
+        let (parent, name) = Self::split(&components)?;
+        let entries = self.directory_mut(parent, access_level)?;
+
+        match entries.get(name) {
+            None => Err(FileSystemError::NotFound),
+            Some(node) if !node.can_write(access_level) => Err(FileSystemError::PermissionDenied),
+            Some(Node::Directory {
+                entries: children, ..
+            }) if !children.is_empty() => Err(FileSystemError::DirectoryNotEmpty),
+            Some(_) => {
+                entries.remove(name);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn copy(
+        &mut self,
+        cwd: &str,
+        from: &str,
+        to: &str,
+        access_level: AccessLevel,
+    ) -> Result<(), FileSystemError> {
+        let contents = self.read(cwd, from, access_level)?.to_string();
+        let to = self.target(cwd, from, to, access_level);
+
+        self.write(cwd, &to, &contents, access_level)
+    }
+
+    pub fn rename(
+        &mut self,
+        cwd: &str,
+        from: &str,
+        to: &str,
+        access_level: AccessLevel,
+    ) -> Result<(), FileSystemError> {
+        let from_components = Self::resolve(cwd, from);
+        let to = self.target(cwd, from, to, access_level);
+        let to_components = Self::resolve(cwd, &to);
+
+        if to_components.starts_with(&from_components) {
+            return Err(FileSystemError::PermissionDenied);
+        }
+
+        let (to_parent, to_name) = Self::split(&to_components)?;
+
+        if self
+            .directory_mut(to_parent, access_level)?
+            .contains_key(to_name)
+        {
+            return Err(FileSystemError::AlreadyExists);
+        }
+
+        let (from_parent, from_name) = Self::split(&from_components)?;
+        let entries = self.directory_mut(from_parent, access_level)?;
+
+        let node = match entries.get(from_name) {
+            None => return Err(FileSystemError::NotFound),
+            Some(node) if !node.can_write(access_level) => {
+                return Err(FileSystemError::PermissionDenied)
+            }
+            Some(_) => entries.remove(from_name).expect("entry exists"),
+        };
+
+        self.directory_mut(to_parent, access_level)?
+            .insert(to_name.clone(), node);
+
+        Ok(())
+    }
+
+    pub fn target(&self, cwd: &str, from: &str, to: &str, access_level: AccessLevel) -> String {
+        if !self.is_directory(cwd, to, access_level) {
+            return to.to_string();
+        }
+
+        match Self::resolve(cwd, from).last() {
+            Some(name) => format!("{to}/{name}"),
+            None => to.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_handles_relative_paths_and_parents() {
+        assert_eq!(
+            FileSystem::resolve("/home", "notes.txt"),
+            ["home", "notes.txt"]
+        );
+        assert_eq!(FileSystem::resolve("/home", "../etc/./app"), ["etc", "app"]);
+        assert_eq!(FileSystem::resolve("/home", "/.."), Vec::<String>::new());
+    }
+
+    #[test]
+    fn write_then_read() {
+        let mut filesystem = FileSystem::default();
+
+        filesystem
+            .write("/home", "notes.txt", "hello", AccessLevel::User)
+            .unwrap();
+
+        assert_eq!(
+            filesystem.read("/", "/home/notes.txt", AccessLevel::User),
+            Ok("hello")
+        );
+        assert_eq!(
+            filesystem.list("/", "/home", AccessLevel::User),
+            Ok(vec![String::from("notes.txt")])
+        );
+    }
+
+    #[test]
+    fn permissions_are_enforced() {
+        let mut filesystem = FileSystem::default();
+
+        filesystem
+            .create_file(
+                "/",
+                "/etc/secret",
+                "hunter2",
+                Permissions::restricted(AccessLevel::Operator),
+                AccessLevel::Root,
+            )
+            .unwrap();
+
+        assert_eq!(
+            filesystem.read("/", "/etc/secret", AccessLevel::User),
+            Err(FileSystemError::PermissionDenied)
+        );
+        assert_eq!(
+            filesystem.write("/", "/etc/other", "", AccessLevel::User),
+            Err(FileSystemError::PermissionDenied)
+        );
+        assert_eq!(
+            filesystem.read("/", "/etc/secret", AccessLevel::Operator),
+            Ok("hunter2")
+        );
+    }
+
+    #[test]
+    fn missing_and_mistyped_paths() {
+        let mut filesystem = FileSystem::default();
+
+        assert_eq!(
+            filesystem.read("/", "/home/missing", AccessLevel::Root),
+            Err(FileSystemError::NotFound)
+        );
+        assert_eq!(
+            filesystem.read("/", "/home", AccessLevel::Root),
+            Err(FileSystemError::IsADirectory)
+        );
+
+        filesystem
+            .write("/", "/tmp/file", "", AccessLevel::User)
+            .unwrap();
+
+        assert_eq!(
+            filesystem.change_directory("/", "/tmp/file", AccessLevel::User),
+            Err(FileSystemError::NotADirectory)
+        );
+    }
+
+    #[test]
+    fn remove_refuses_non_empty_directories() {
+        let mut filesystem = FileSystem::default();
+
+        filesystem
+            .make_directory("/", "/tmp/dir", AccessLevel::User)
+            .unwrap();
+        filesystem
+            .write("/", "/tmp/dir/file", "", AccessLevel::User)
+            .unwrap();
+
+        assert_eq!(
+            filesystem.remove("/", "/tmp/dir", AccessLevel::User),
+            Err(FileSystemError::DirectoryNotEmpty)
+        );
+
+        filesystem
+            .remove("/", "/tmp/dir/file", AccessLevel::User)
+            .unwrap();
+        filesystem
+            .remove("/", "/tmp/dir", AccessLevel::User)
+            .unwrap();
+
+        assert!(!filesystem.is_directory("/", "/tmp/dir", AccessLevel::User));
+    }
+
+    #[test]
+    fn copy_and_rename_into_directories() {
+        let mut filesystem = FileSystem::default();
+
+        filesystem
+            .write("/", "/tmp/file", "contents", AccessLevel::User)
+            .unwrap();
+        filesystem
+            .copy("/", "/tmp/file", "/home", AccessLevel::User)
+            .unwrap();
+        filesystem
+            .rename("/tmp", "file", "moved", AccessLevel::User)
+            .unwrap();
+
+        assert_eq!(
+            filesystem.read("/", "/home/file", AccessLevel::User),
+            Ok("contents")
+        );
+        assert_eq!(
+            filesystem.read("/", "/tmp/moved", AccessLevel::User),
+            Ok("contents")
+        );
+        assert_eq!(
+            filesystem.rename("/", "/tmp", "/tmp/inside", AccessLevel::Root),
+            Err(FileSystemError::PermissionDenied)
+        );
+    }
+
+    #[test]
+    fn append_and_truncate_front() {
+        let mut filesystem = FileSystem::default();
+
+        assert_eq!(
+            filesystem.append("/", "/tmp/log", "one\n", AccessLevel::User),
+            Err(FileSystemError::NotFound)
+        );
+
+        filesystem
+            .write("/", "/tmp/log", "one\n", AccessLevel::User)
+            .unwrap();
+        filesystem
+            .append("/", "/tmp/log", "two\nthree\n", AccessLevel::User)
+            .unwrap();
+
+        assert_eq!(
+            filesystem.read("/", "/tmp/log", AccessLevel::User),
+            Ok("one\ntwo\nthree\n")
+        );
+
+        filesystem
+            .truncate_front("/", "/tmp/log", 8, AccessLevel::User)
+            .unwrap();
+
+        assert_eq!(
+            filesystem.read("/", "/tmp/log", AccessLevel::User),
+            Ok("three\n")
+        );
+        assert_eq!(
+            filesystem.append("/", "/tmp", "x", AccessLevel::Root),
+            Err(FileSystemError::IsADirectory)
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use crate::shell::model::AccessLevel;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub access_level: AccessLevel,
+    pub command: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Evidence {
+    AuditTrail(usize),
+    FailedLogins(usize),
+    HistoryFile(String),
+    OpenSession(AccessLevel),
+    OpenConnection(String),
+    VisibleScreen,
+}
+
+impl std::fmt::Display for Evidence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Evidence::AuditTrail(entries) => {
+                write!(f, "{entries} privileged entries in the audit log")
+            }
+            Evidence::FailedLogins(attempts) => write!(f, "{attempts} failed login attempts"),
+            Evidence::HistoryFile(path) => write!(f, "commands left in '{path}'"),
+            Evidence::OpenSession(access_level) => {
+                write!(f, "terminal left logged in as {access_level}")
+            }
+            Evidence::OpenConnection(hostname) => {
+                write!(f, "connection to {hostname} left open")
+            }
+            Evidence::VisibleScreen => write!(f, "output left on the screen"),
+        }
+    }
+}
+
+pub fn history_file(access_level: AccessLevel) -> &'static str {
+    match access_level {
+        AccessLevel::Guest | AccessLevel::User => "/home/.history",
+        _ => "/root/.history",
+    }
+}
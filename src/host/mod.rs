@@ -0,0 +1,37 @@
+use self::{
+    controller::{monitor_hosts, tick_databases, tick_hosts, tick_services, write_logs},
+    model::{DatabaseEvent, DeploymentEvent, MonitoringAlert, ServiceEvent},
+    network::Network,
+};
+use bevy::prelude::*;
+
+pub mod controller;
+pub mod database;
+pub mod filesystem;
+pub mod forensics;
+pub mod model;
+pub mod monitoring;
+pub mod network;
+pub mod process;
+pub mod release;
+pub mod state;
+pub mod view;
+
+pub struct HostPlugin;
+
+impl Plugin for HostPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Network>()
+            .add_event::<DeploymentEvent>()
+            .add_event::<DatabaseEvent>()
+            .add_event::<ServiceEvent>()
+            .add_event::<MonitoringAlert>()
+            .add_systems((
+                tick_hosts,
+                tick_databases,
+                tick_services,
+                write_logs,
+                monitor_hosts,
+            ));
+    }
+}
@@ -0,0 +1,202 @@
+use bevy::prelude::*;
+
+use super::{
+    database::MigrationEvent,
+    filesystem::{FileSystem, FileSystemError, Permissions},
+    forensics::{history_file, AuditEntry, Evidence},
+    monitoring::{Alert, LogFile, Monitor},
+    process::ServiceState,
+    release::DeploymentOutcome,
+    state::HostState,
+};
+use crate::shell::model::AccessLevel;
+
+const LOG_BYTES: usize = 64 * 1024;
+
+pub struct DeploymentEvent {
+    pub hostname: String,
+    pub version: String,
+    pub outcome: DeploymentOutcome,
+}
+
+pub struct DatabaseEvent {
+    pub hostname: String,
+    pub event: MigrationEvent,
+}
+
+pub struct ServiceEvent {
+    pub hostname: String,
+    pub service: String,
+    pub state: ServiceState,
+}
+
+#[derive(Component, Default, Clone, Debug)]
+pub struct Host {
+    pub filesystem: FileSystem,
+    pub audit: Vec<AuditEntry>,
+    pub failed_logins: usize,
+    pub state: HostState,
+    pub monitor: Monitor,
+}
+
+pub struct MonitoringAlert {
+    pub hostname: String,
+    pub alert: Alert,
+}
+
+impl Host {
+    pub fn new(hostname: &str) -> Self {
+        let mut host = Self::default();
+
+        host.add_file(
+            "/etc/hostname",
+            hostname,
+            Permissions::new(AccessLevel::Guest, AccessLevel::Root),
+        )
+        .expect("a new host has an /etc directory");
+
+        host
+    }
+
+    pub fn add_file(
+        &mut self,
+        path: &str,
+        contents: &str,
+        permissions: Permissions,
+    ) -> Result<(), FileSystemError> {
+        if let Some((parent, _)) = path.rsplit_once('/') {
+            self.filesystem.create_directory(
+                "/",
+                parent,
+                Permissions::new(AccessLevel::Guest, permissions.write),
+            )?;
+        }
+
+        self.filesystem
+            .create_file("/", path, contents, permissions, AccessLevel::Root)
+    }
+
+    pub fn record(&mut self, access_level: AccessLevel, command: &str) {
+        self.audit.push(AuditEntry {
+            access_level,
+            command: command.to_string(),
+        });
+        self.log(LogFile::Commands, &format!("{access_level}: {command}"));
+    }
+
+    pub fn log(&mut self, file: LogFile, message: &str) {
+        let line = format!("[{:>10.1}] {message}\n", self.state.uptime);
+
+        if self
+            .filesystem
+            .append("/", file.path(), &line, AccessLevel::Root)
+            .is_err()
+        {
+            self.add_file(
+                file.path(),
+                &line,
+                Permissions::new(AccessLevel::Operator, AccessLevel::Root),
+            )
+            .ok();
+        }
+
+        // Like a rotated log, a full one drops its oldest half instead of growing forever
+        let length = self
+            .filesystem
+            .read("/", file.path(), AccessLevel::Root)
+            .map_or(0, str::len);
+
+        if length > LOG_BYTES {
+            self.filesystem
+                .truncate_front("/", file.path(), LOG_BYTES / 2, AccessLevel::Root)
+                .ok();
+        }
+    }
+
+    pub fn monitor(&mut self, delta: f32) -> Vec<Alert> {
+        self.monitor.tick(delta, &self.filesystem)
+    }
+
+    pub fn append_history(&mut self, access_level: AccessLevel, command: &str) {
+        let path = history_file(access_level);
+        let history = match self.filesystem.read("/", path, AccessLevel::Root) {
+            Ok(history) => format!("{history}{command}\n"),
+            Err(_) => format!("{command}\n"),
+        };
+
+        self.add_file(path, &history, Permissions::restricted(access_level))
+            .ok();
+    }
+
+    pub fn relevel(&mut self, command: &str, from: AccessLevel, to: AccessLevel) {
+        if let Some(entry) = self
+            .audit
+            .last_mut()
+            .filter(|entry| entry.command == command && entry.access_level == from)
+        {
+            entry.access_level = to;
+        }
+    }
+
+    pub fn move_history(&mut self, command: &str, from: AccessLevel, to: AccessLevel) {
+        let path = history_file(from);
+
+        if path == history_file(to) {
+            return;
+        }
+
+        let Some(history) = self
+            .filesystem
+            .read("/", path, AccessLevel::Root)
+            .ok()
+            .and_then(|history| history.strip_suffix(&format!("{command}\n")))
+            .map(str::to_string)
+        else {
+            return;
+        };
+
+        if self
+            .filesystem
+            .write("/", path, &history, AccessLevel::Root)
+            .is_ok()
+        {
+            self.append_history(to, command);
+        }
+    }
+
+    pub fn clear_history(&mut self) {
+        for access_level in [AccessLevel::User, AccessLevel::Root] {
+            self.filesystem
+                .remove("/", history_file(access_level), AccessLevel::Root)
+                .ok();
+        }
+    }
+
+    pub fn evidence(&self) -> Vec<Evidence> {
+        let mut evidence = Vec::new();
+
+        let privileged = self
+            .audit
+            .iter()
+            .filter(|entry| entry.access_level > AccessLevel::User)
+            .count();
+
+        if privileged > 0 {
+            evidence.push(Evidence::AuditTrail(privileged));
+        }
+
+        if self.failed_logins > 0 {
+            evidence.push(Evidence::FailedLogins(self.failed_logins));
+        }
+
+        let path = history_file(AccessLevel::Root);
+
+        if let Ok(history) = self.filesystem.read("/", path, AccessLevel::Root) {
+            if !history.is_empty() {
+                evidence.push(Evidence::HistoryFile(path.to_string()));
+            }
+        }
+
+        evidence
+    }
+}
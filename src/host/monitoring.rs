@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+
+use super::filesystem::FileSystem;
+use crate::shell::model::AccessLevel;
+
+const SCAN_SECONDS: f32 = 15.;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFile {
+    Auth,
+    Commands,
+    Syslog,
+}
+
+impl LogFile {
+    pub const ALL: [LogFile; 3] = [LogFile::Auth, LogFile::Commands, LogFile::Syslog];
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            LogFile::Auth => "/var/log/auth.log",
+            LogFile::Commands => "/var/log/commands.log",
+            LogFile::Syslog => "/var/log/syslog",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlertRule {
+    FailedLogin,
+    ServiceFailure,
+    ServiceRestart,
+    VersionChange,
+    MigrationFailure,
+    DatabaseLocked,
+}
+
+impl AlertRule {
+    pub fn matching(file: LogFile, line: &str) -> Option<Self> {
+        let rule = match file {
+            LogFile::Auth if line.contains("login failed") => AlertRule::FailedLogin,
+            LogFile::Syslog if line.contains("deployed version") => AlertRule::VersionChange,
+            LogFile::Syslog if line.contains("migration") && line.contains("failed") => {
+                AlertRule::MigrationFailure
+            }
+            LogFile::Syslog if line.contains("database locked") => AlertRule::DatabaseLocked,
+            LogFile::Syslog if line.ends_with("entered failed state") => AlertRule::ServiceFailure,
+            LogFile::Syslog if line.ends_with(" started") || line.ends_with(" stopped") => {
+                AlertRule::ServiceRestart
+            }
+            _ => return None,
+        };
+
+        Some(rule)
+    }
+
+    pub fn severity(&self) -> f32 {
+        match self {
+            AlertRule::FailedLogin => 1.,
+            AlertRule::ServiceFailure => 2.,
+            AlertRule::ServiceRestart => 1.,
+            AlertRule::VersionChange => 2.,
+            AlertRule::MigrationFailure => 3.,
+            AlertRule::DatabaseLocked => 1.,
+        }
+    }
+}
+
+impl std::fmt::Display for AlertRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlertRule::FailedLogin => write!(f, "failed login"),
+            AlertRule::ServiceFailure => write!(f, "service failure"),
+            AlertRule::ServiceRestart => write!(f, "unexpected restart"),
+            AlertRule::VersionChange => write!(f, "version change"),
+            AlertRule::MigrationFailure => write!(f, "migration failure"),
+            AlertRule::DatabaseLocked => write!(f, "database locked"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alert {
+    pub rule: AlertRule,
+    pub line: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Monitor {
+    last_lines: BTreeMap<&'static str, String>,
+    elapsed: f32,
+}
+
+impl Monitor {
+    pub fn tick(&mut self, delta: f32, filesystem: &FileSystem) -> Vec<Alert> {
+        self.elapsed += delta;
+
+        if self.elapsed < SCAN_SECONDS {
+            return Vec::new();
+        }
+
+        self.elapsed = 0.;
+        self.scan(filesystem)
+    }
+
+    fn scan(&mut self, filesystem: &FileSystem) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+
+        for file in LogFile::ALL {
+            let lines = filesystem
+                .read("/", file.path(), AccessLevel::Root)
+                .map(|contents| contents.lines().collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            // Logs are capped and can be emptied or removed, so new lines are the ones after the
+            // last line seen, or every line once that one is gone
+            let start = self
+                .last_lines
+                .get(file.path())
+                .and_then(|last| lines.iter().rposition(|line| line == last))
+                .map_or(0, |idx| idx + 1);
+
+            alerts.extend(lines[start..].iter().filter_map(|line| {
+                AlertRule::matching(file, line).map(|rule| Alert {
+                    rule,
+                    line: line.to_string(),
+                })
+            }));
+
+            match lines.last() {
+                Some(line) => self.last_lines.insert(file.path(), line.to_string()),
+                None => self.last_lines.remove(file.path()),
+            };
+        }
+
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::model::Host;
+
+    fn rules(host: &mut Host) -> Vec<AlertRule> {
+        host.monitor
+            .scan(&host.filesystem)
+            .into_iter()
+            .map(|alert| alert.rule)
+            .collect()
+    }
+
+    #[test]
+    fn specific_rules_match_before_generic_ones() {
+        assert_eq!(
+            AlertRule::matching(LogFile::Syslog, "migration to schema 4 failed"),
+            Some(AlertRule::MigrationFailure)
+        );
+        assert_eq!(
+            AlertRule::matching(LogFile::Syslog, "deployed version 2.0.0, api started"),
+            Some(AlertRule::VersionChange)
+        );
+        assert_eq!(
+            AlertRule::matching(LogFile::Syslog, "database locked, api entered failed state"),
+            Some(AlertRule::DatabaseLocked)
+        );
+        assert_eq!(
+            AlertRule::matching(LogFile::Syslog, "api entered failed state"),
+            Some(AlertRule::ServiceFailure)
+        );
+        assert_eq!(
+            AlertRule::matching(LogFile::Syslog, "api started"),
+            Some(AlertRule::ServiceRestart)
+        );
+        assert_eq!(
+            AlertRule::matching(LogFile::Auth, "login failed for ops"),
+            Some(AlertRule::FailedLogin)
+        );
+        assert_eq!(
+            AlertRule::matching(LogFile::Syslog, "login failed for ops"),
+            None
+        );
+    }
+
+    #[test]
+    fn lines_are_only_reported_once() {
+        let mut host = Host::new("test01");
+
+        host.log(LogFile::Auth, "login failed for ops");
+
+        assert_eq!(rules(&mut host), [AlertRule::FailedLogin]);
+        assert_eq!(rules(&mut host), []);
+
+        host.log(LogFile::Syslog, "api started");
+
+        assert_eq!(rules(&mut host), [AlertRule::ServiceRestart]);
+    }
+
+    #[test]
+    fn truncated_logs_are_read_from_the_start() {
+        let mut host = Host::new("test01");
+
+        host.log(LogFile::Syslog, "api started");
+        host.log(LogFile::Syslog, "api stopped");
+        host.log(LogFile::Syslog, "api started");
+        rules(&mut host);
+
+        host.filesystem
+            .write("/", LogFile::Syslog.path(), "", AccessLevel::Root)
+            .unwrap();
+        host.state.uptime = 60.;
+
+        for _ in 0..4 {
+            host.log(LogFile::Syslog, "api entered failed state");
+        }
+
+        assert_eq!(rules(&mut host), [AlertRule::ServiceFailure; 4]);
+
+        host.filesystem
+            .remove("/", LogFile::Syslog.path(), AccessLevel::Root)
+            .unwrap();
+        host.log(LogFile::Syslog, "api started");
+
+        assert_eq!(rules(&mut host), [AlertRule::ServiceRestart]);
+    }
+
+    #[test]
+    fn full_logs_drop_their_oldest_lines() {
+        let mut host = Host::new("test01");
+
+        for idx in 0..5000 {
+            host.state.uptime = f64::from(idx);
+            host.log(LogFile::Commands, "user: ls");
+        }
+
+        rules(&mut host);
+        host.log(LogFile::Auth, "login failed for ops");
+
+        let log = host
+            .filesystem
+            .read("/", LogFile::Commands.path(), AccessLevel::Root)
+            .unwrap();
+
+        assert!(log.len() <= 64 * 1024);
+        assert!(log.starts_with('['));
+        assert!(log.ends_with("[    4999.0] user: ls\n"));
+        assert_eq!(rules(&mut host), [AlertRule::FailedLogin]);
+    }
+}
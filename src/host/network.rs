@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+
+use super::model::Host;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Protocol {
+    Icmp,
+    Ssh,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetworkError {
+    UnknownHost(String),
+    Unreachable(String),
+    ConnectionLost(String),
+}
+
+impl std::fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetworkError::UnknownHost(host) => write!(f, "could not resolve host '{host}'"),
+            NetworkError::Unreachable(host) => write!(f, "connection to {host} timed out"),
+            NetworkError::ConnectionLost(host) => write!(f, "connection to {host} lost"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NetworkHost {
+    pub address: String,
+    pub subnet: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct FirewallRule {
+    pub from: String,
+    pub to: String,
+    pub protocol: Protocol,
+}
+
+#[derive(Resource, Default, Clone, Debug)]
+pub struct Network {
+    hosts: BTreeMap<String, NetworkHost>,
+    subnets: BTreeMap<String, String>,
+    rules: Vec<FirewallRule>,
+}
+
+impl Network {
+    pub fn add_subnet(&mut self, name: &str, prefix: &str) -> &mut Self {
+        self.subnets.insert(name.to_string(), prefix.to_string());
+        self
+    }
+
+    pub fn add_host(&mut self, hostname: &str, subnet: &str, suffix: u8) -> &mut Self {
+        let prefix = self.subnets.get(subnet).cloned().unwrap_or_default();
+
+        self.hosts.insert(
+            hostname.to_string(),
+            NetworkHost {
+                address: format!("{prefix}.{suffix}"),
+                subnet: subnet.to_string(),
+            },
+        );
+        self
+    }
+
+    pub fn allow(&mut self, from: &str, to: &str, protocol: Protocol) -> &mut Self {
+        self.rules.push(FirewallRule {
+            from: from.to_string(),
+            to: to.to_string(),
+            protocol,
+        });
+        self
+    }
+
+    pub fn hostnames(&self) -> impl Iterator<Item = &String> {
+        self.hosts.keys()
+    }
+
+    pub fn resolve<'a>(&'a self, name: &str) -> Result<(&'a str, &'a NetworkHost), NetworkError> {
+        self.hosts
+            .iter()
+            .find(|(hostname, host)| *hostname == name || host.address == name)
+            .map(|(hostname, host)| (hostname.as_str(), host))
+            .ok_or_else(|| NetworkError::UnknownHost(name.to_string()))
+    }
+
+    pub fn connect<'a>(
+        &'a self,
+        from: &str,
+        to: &str,
+        protocol: Protocol,
+    ) -> Result<(&'a str, &'a NetworkHost), NetworkError> {
+        let (_, source) = self.resolve(from)?;
+        let (hostname, target) = self.resolve(to)?;
+
+        let allowed = source.subnet == target.subnet
+            || self.rules.iter().any(|rule| {
+                rule.protocol == protocol && rule.from == source.subnet && rule.to == target.subnet
+            });
+
+        match allowed {
+            true => Ok((hostname, target)),
+            false => Err(NetworkError::Unreachable(to.to_string())),
+        }
+    }
+}
+
+pub struct Hosts<'a> {
+    pub network: &'a Network,
+    hosts: BTreeMap<String, &'a mut Host>,
+    current: String,
+}
+
+impl<'a> Hosts<'a> {
+    pub fn new(
+        network: &'a Network,
+        hosts: impl IntoIterator<Item = (String, &'a mut Host)>,
+    ) -> Self {
+        Self {
+            network,
+            hosts: hosts.into_iter().collect(),
+            current: String::new(),
+        }
+    }
+
+    pub fn select(&mut self, hostname: &str) -> Result<(), NetworkError> {
+        if !self.hosts.contains_key(hostname) {
+            return Err(NetworkError::ConnectionLost(hostname.to_string()));
+        }
+
+        self.current = hostname.to_string();
+
+        Ok(())
+    }
+
+    pub fn get_mut(&mut self, hostname: &str) -> Option<&mut Host> {
+        self.hosts.get_mut(hostname).map(|host| &mut **host)
+    }
+}
+
+impl std::ops::Deref for Hosts<'_> {
+    type Target = Host;
+
+    fn deref(&self) -> &Self::Target {
+        self.hosts
+            .get(&self.current)
+            .expect("a host is selected before commands run")
+    }
+}
+
+impl std::ops::DerefMut for Hosts<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.hosts
+            .get_mut(&self.current)
+            .expect("a host is selected before commands run")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network() -> Network {
+        let mut network = Network::default();
+
+        network
+            .add_subnet("office", "10.0.1")
+            .add_subnet("datacenter", "10.0.2")
+            .add_host("desk", "office", 11)
+            .add_host("printer", "office", 12)
+            .add_host("db01", "datacenter", 21)
+            .allow("office", "datacenter", Protocol::Ssh);
+
+        network
+    }
+
+    #[test]
+    fn hosts_resolve_by_name_or_address() {
+        let network = network();
+
+        assert_eq!(
+            network
+                .resolve("10.0.2.21")
+                .map(|(hostname, host)| (hostname, host.address.as_str())),
+            Ok(("db01", "10.0.2.21"))
+        );
+        assert_eq!(
+            network.resolve("nope").map(|(hostname, _)| hostname),
+            Err(NetworkError::UnknownHost(String::from("nope")))
+        );
+    }
+
+    #[test]
+    fn hosts_on_the_same_subnet_can_always_connect() {
+        let network = network();
+
+        assert!(network.connect("desk", "printer", Protocol::Icmp).is_ok());
+        assert!(network.connect("desk", "printer", Protocol::Ssh).is_ok());
+    }
+
+    #[test]
+    fn firewall_rules_match_direction_and_protocol() {
+        let network = network();
+
+        assert_eq!(
+            network
+                .connect("desk", "db01", Protocol::Ssh)
+                .map(|(hostname, _)| hostname),
+            Ok("db01")
+        );
+        assert_eq!(
+            network
+                .connect("desk", "db01", Protocol::Icmp)
+                .map(|(hostname, _)| hostname),
+            Err(NetworkError::Unreachable(String::from("db01")))
+        );
+        assert_eq!(
+            network
+                .connect("db01", "desk", Protocol::Ssh)
+                .map(|(hostname, _)| hostname),
+            Err(NetworkError::Unreachable(String::from("desk")))
+        );
+        assert_eq!(
+            network
+                .connect("desk", "db02", Protocol::Ssh)
+                .map(|(hostname, _)| hostname),
+            Err(NetworkError::UnknownHost(String::from("db02")))
+        );
+    }
+}
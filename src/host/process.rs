@@ -0,0 +1,519 @@
+use std::collections::HashSet;
+
+use crate::shell::model::AccessLevel;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServiceState {
+    Active,
+    Inactive,
+    Failed,
+}
+
+impl std::fmt::Display for ServiceState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServiceState::Active => write!(f, "active"),
+            ServiceState::Inactive => write!(f, "inactive"),
+            ServiceState::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ServiceError {
+    UnknownService(String),
+    UnknownProcess(u32),
+    PermissionDenied,
+    AlreadyActive(String),
+    NotActive(String),
+    DependencyInactive(String),
+    StartFailed(String),
+}
+
+impl std::fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServiceError::UnknownService(name) => write!(f, "unit {name} not found"),
+            ServiceError::UnknownProcess(pid) => write!(f, "no such process {pid}"),
+            ServiceError::PermissionDenied => write!(f, "operation not permitted"),
+            ServiceError::AlreadyActive(name) => write!(f, "{name} is already active"),
+            ServiceError::NotActive(name) => write!(f, "{name} is not active"),
+            ServiceError::DependencyInactive(name) => write!(f, "dependency {name} is not active"),
+            ServiceError::StartFailed(name) => write!(f, "{name} failed to start"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Service {
+    pub name: String,
+    pub state: ServiceState,
+    pub owner: AccessLevel,
+    pub requires: Vec<String>,
+    pub release: bool,
+}
+
+impl Service {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: ServiceState::Inactive,
+            owner: AccessLevel::Root,
+            requires: Vec::new(),
+            release: false,
+        }
+    }
+
+    pub fn owner(mut self, owner: AccessLevel) -> Self {
+        self.owner = owner;
+        self
+    }
+
+    pub fn requires(mut self, service: &str) -> Self {
+        self.requires.push(service.to_string());
+        self
+    }
+
+    pub fn release(mut self) -> Self {
+        self.release = true;
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Process {
+    pub pid: u32,
+    pub owner: AccessLevel,
+    pub command: String,
+    pub service: Option<String>,
+    pub cpu: f32,
+    pub memory: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct ProcessTable {
+    services: Vec<Service>,
+    processes: Vec<Process>,
+    next_pid: u32,
+    changes: Vec<(String, ServiceState)>,
+}
+
+impl Default for ProcessTable {
+    fn default() -> Self {
+        Self {
+            services: Vec::new(),
+            processes: vec![
+                Process {
+                    pid: 1,
+                    owner: AccessLevel::Root,
+                    command: String::from("/sbin/init"),
+                    service: None,
+                    cpu: 0.,
+                    memory: 0.1,
+                },
+                Process {
+                    pid: 412,
+                    owner: AccessLevel::Root,
+                    command: String::from("/usr/sbin/sshd"),
+                    service: None,
+                    cpu: 0.,
+                    memory: 0.2,
+                },
+            ],
+            next_pid: 1024,
+            changes: Vec::new(),
+        }
+    }
+}
+
+impl ProcessTable {
+    pub fn new(services: Vec<Service>) -> Self {
+        let mut table = Self::default();
+
+        for service in services {
+            let name = service.name.clone();
+
+            table.services.push(service);
+            table.spawn(&name);
+        }
+
+        table.changes.clear();
+        table
+    }
+
+    pub fn services(&self) -> impl Iterator<Item = &Service> {
+        self.services.iter()
+    }
+
+    pub fn processes(&self) -> impl Iterator<Item = &Process> {
+        self.processes.iter()
+    }
+
+    pub fn is_active(&self, name: &str) -> bool {
+        self.service(name)
+            .is_some_and(|service| service.state == ServiceState::Active)
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.services
+            .iter()
+            .filter(|service| service.state == ServiceState::Active)
+            .count()
+    }
+
+    fn service(&self, name: &str) -> Option<&Service> {
+        self.services.iter().find(|service| service.name == name)
+    }
+
+    fn set_state(&mut self, name: &str, state: ServiceState) {
+        let Some(service) = self
+            .services
+            .iter_mut()
+            .find(|service| service.name == name)
+        else {
+            return;
+        };
+
+        if service.state == state {
+            return;
+        }
+
+        service.state = state;
+        self.changes.push((name.to_string(), state));
+
+        if state != ServiceState::Active {
+            self.processes
+                .retain(|process| process.service.as_deref() != Some(name));
+        }
+    }
+
+    fn spawn(&mut self, name: &str) {
+        let Some(service) = self.service(name) else {
+            return;
+        };
+
+        let pid = self.next_pid;
+        let process = Process {
+            pid,
+            owner: service.owner,
+            command: format!("/usr/sbin/{name}"),
+            service: Some(name.to_string()),
+            cpu: 0.,
+            memory: 1. + (pid % 13) as f32 * 0.7,
+        };
+
+        self.next_pid += 1 + pid % 17;
+        self.processes.push(process);
+        self.set_state(name, ServiceState::Active);
+    }
+
+    pub fn start(&mut self, name: &str, crash_loop: bool) -> Result<(), ServiceError> {
+        let service = self
+            .service(name)
+            .ok_or_else(|| ServiceError::UnknownService(name.to_string()))?;
+
+        if service.state == ServiceState::Active {
+            return Err(ServiceError::AlreadyActive(name.to_string()));
+        }
+
+        if let Some(dependency) = service
+            .requires
+            .iter()
+            .find(|dependency| !dependency.contains(':') && !self.is_active(dependency))
+        {
+            return Err(ServiceError::DependencyInactive(dependency.clone()));
+        }
+
+        if service.release && crash_loop {
+            self.set_state(name, ServiceState::Failed);
+            return Err(ServiceError::StartFailed(name.to_string()));
+        }
+
+        self.spawn(name);
+
+        Ok(())
+    }
+
+    pub fn stop(&mut self, name: &str) -> Result<(), ServiceError> {
+        if !self.is_active(name) {
+            return match self.service(name) {
+                Some(_) => Err(ServiceError::NotActive(name.to_string())),
+                None => Err(ServiceError::UnknownService(name.to_string())),
+            };
+        }
+
+        self.set_state(name, ServiceState::Inactive);
+
+        Ok(())
+    }
+
+    pub fn restart(&mut self, name: &str, crash_loop: bool) -> Result<(), ServiceError> {
+        if self.is_active(name) {
+            self.set_state(name, ServiceState::Inactive);
+        }
+
+        self.start(name, crash_loop)
+    }
+
+    pub fn fail(&mut self, name: &str) {
+        self.set_state(name, ServiceState::Failed);
+    }
+
+    pub fn fail_releases(&mut self) {
+        let releases = self
+            .services
+            .iter()
+            .filter(|service| service.release)
+            .map(|service| service.name.clone())
+            .collect::<Vec<_>>();
+
+        for name in releases {
+            self.fail(&name);
+        }
+    }
+
+    pub fn start_releases(&mut self) {
+        let releases = self
+            .services
+            .iter()
+            .filter(|service| service.release && service.state != ServiceState::Active)
+            .map(|service| service.name.clone())
+            .collect::<Vec<_>>();
+
+        for name in releases {
+            self.start(&name, false).ok();
+        }
+    }
+
+    pub fn kill(
+        &mut self,
+        pid: u32,
+        access_level: AccessLevel,
+    ) -> Result<Option<String>, ServiceError> {
+        let process = self
+            .processes
+            .iter()
+            .find(|process| process.pid == pid)
+            .ok_or(ServiceError::UnknownProcess(pid))?;
+
+        if pid == 1 || access_level < process.owner {
+            return Err(ServiceError::PermissionDenied);
+        }
+
+        let service = process.service.clone();
+
+        match &service {
+            Some(name) => self.fail(name),
+            None => self.processes.retain(|process| process.pid != pid),
+        }
+
+        Ok(service)
+    }
+
+    pub fn active(&self, hostname: &str) -> impl Iterator<Item = String> + '_ {
+        let hostname = hostname.to_string();
+
+        self.services
+            .iter()
+            .filter(|service| service.state == ServiceState::Active)
+            .map(move |service| format!("{hostname}:{}", service.name))
+    }
+
+    pub fn fail_unmet(&mut self, hostname: &str, active: &HashSet<String>) {
+        let unmet = self
+            .services
+            .iter()
+            .filter(|service| service.state == ServiceState::Active)
+            .filter(|service| {
+                service.requires.iter().any(|dependency| {
+                    let qualified = match dependency.contains(':') {
+                        true => dependency.clone(),
+                        false => format!("{hostname}:{dependency}"),
+                    };
+
+                    !active.contains(&qualified)
+                })
+            })
+            .map(|service| service.name.clone())
+            .collect::<Vec<_>>();
+
+        for name in unmet {
+            self.fail(&name);
+        }
+    }
+
+    pub fn tick(&mut self, uptime: f64) {
+        for process in &mut self.processes {
+            process.cpu = match process.service {
+                Some(_) => {
+                    2. + 1.5 * (uptime / 7. + f64::from(process.pid)).sin().abs() as f32 * 4.
+                }
+                None => 0.1 * (uptime + f64::from(process.pid)).sin().abs() as f32,
+            };
+        }
+    }
+
+    pub fn drain_changes(&mut self) -> Vec<(String, ServiceState)> {
+        std::mem::take(&mut self.changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> ProcessTable {
+        ProcessTable::new(vec![
+            Service::new("app")
+                .owner(AccessLevel::Operator)
+                .requires("db01:postgres")
+                .release(),
+            Service::new("nginx").requires("app"),
+        ])
+    }
+
+    fn pid(table: &ProcessTable, name: &str) -> u32 {
+        table
+            .processes()
+            .find(|process| process.service.as_deref() == Some(name))
+            .map(|process| process.pid)
+            .expect("service has a process")
+    }
+
+    #[test]
+    fn new_services_start_active_without_changes() {
+        let mut table = table();
+
+        assert_eq!(table.active_count(), 2);
+        assert_eq!(table.processes().count(), 4);
+        assert_eq!(table.drain_changes(), vec![]);
+    }
+
+    #[test]
+    fn start_checks_local_dependencies() {
+        let mut table = table();
+
+        table.stop("app").unwrap();
+        table.stop("nginx").unwrap();
+
+        assert_eq!(
+            table.start("nginx", false),
+            Err(ServiceError::DependencyInactive(String::from("app")))
+        );
+        assert_eq!(
+            table.stop("nginx"),
+            Err(ServiceError::NotActive(String::from("nginx")))
+        );
+        assert_eq!(
+            table.start("cron", false),
+            Err(ServiceError::UnknownService(String::from("cron")))
+        );
+
+        table.start("app", false).unwrap();
+        table.start("nginx", false).unwrap();
+
+        assert_eq!(
+            table.drain_changes(),
+            vec![
+                (String::from("app"), ServiceState::Inactive),
+                (String::from("nginx"), ServiceState::Inactive),
+                (String::from("app"), ServiceState::Active),
+                (String::from("nginx"), ServiceState::Active),
+            ]
+        );
+    }
+
+    #[test]
+    fn crash_loop_fails_releases() {
+        let mut table = table();
+
+        assert_eq!(
+            table.restart("app", true),
+            Err(ServiceError::StartFailed(String::from("app")))
+        );
+        assert!(!table.is_active("app"));
+
+        assert_eq!(
+            table.restart("nginx", true),
+            Err(ServiceError::DependencyInactive(String::from("app")))
+        );
+    }
+
+    #[test]
+    fn kill_respects_owners() {
+        let mut table = table();
+        let app = pid(&table, "app");
+
+        assert_eq!(
+            table.kill(1, AccessLevel::Root),
+            Err(ServiceError::PermissionDenied)
+        );
+        assert_eq!(
+            table.kill(app, AccessLevel::User),
+            Err(ServiceError::PermissionDenied)
+        );
+        assert_eq!(
+            table.kill(app, AccessLevel::Operator),
+            Ok(Some(String::from("app")))
+        );
+        assert_eq!(
+            table.kill(app, AccessLevel::Operator),
+            Err(ServiceError::UnknownProcess(app))
+        );
+        assert_eq!(table.kill(412, AccessLevel::Root), Ok(None));
+        assert_eq!(
+            table.drain_changes(),
+            vec![(String::from("app"), ServiceState::Failed)]
+        );
+    }
+
+    #[test]
+    fn fail_unmet_follows_dependencies_across_hosts() {
+        let mut table = table();
+        let mut active = table.active("web01").collect::<HashSet<_>>();
+
+        active.insert(String::from("db01:postgres"));
+        table.fail_unmet("web01", &active);
+
+        assert_eq!(table.active_count(), 2);
+
+        active.remove("db01:postgres");
+        table.fail_unmet("web01", &active);
+
+        assert!(!table.is_active("app"));
+
+        let active = table.active("web01").collect::<HashSet<_>>();
+        table.fail_unmet("web01", &active);
+
+        assert_eq!(table.active_count(), 0);
+    }
+
+    #[test]
+    fn releases_fail_and_start_together() {
+        let mut table = table();
+
+        table.fail_releases();
+
+        assert!(!table.is_active("app"));
+        assert!(table.is_active("nginx"));
+
+        table.start_releases();
+
+        assert!(table.is_active("app"));
+    }
+
+    #[test]
+    fn tick_only_moves_cpu_usage() {
+        let mut table = table();
+
+        table.tick(86400.);
+
+        assert!(table
+            .processes()
+            .all(|process| (0. ..=8.).contains(&process.cpu)));
+        assert!(table
+            .processes()
+            .filter(|process| process.service.is_some())
+            .all(|process| process.cpu >= 2.));
+    }
+}
@@ -0,0 +1,233 @@
+use std::cmp::Ordering;
+
+const STAGE_SECONDS: f32 = 5.;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Failure {
+    CrashLoop,
+    DataLoss,
+}
+
+impl std::fmt::Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::CrashLoop => write!(f, "crash loop"),
+            Failure::DataLoss => write!(f, "data loss"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Release {
+    pub version: String,
+    pub requires: Option<String>,
+    pub schema_version: Option<u32>,
+    pub failure: Option<Failure>,
+}
+
+impl Release {
+    pub fn new(version: &str) -> Self {
+        Self {
+            version: version.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+fn compare_identifiers(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u32>(), b.parse::<u32>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+fn parse_version(version: &str) -> (Vec<u32>, Option<&str>) {
+    let (core, pre_release) = match version.split_once('-') {
+        Some((core, pre_release)) => (core, Some(pre_release)),
+        None => (version, None),
+    };
+
+    let core = core
+        .split('.')
+        .map(|part| part.parse::<u32>().unwrap_or_default())
+        .collect();
+
+    (core, pre_release)
+}
+
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a_core, a_pre_release) = parse_version(a);
+    let (b_core, b_pre_release) = parse_version(b);
+
+    // Like semver, a pre-release such as `2.5.0-rc1` comes before `2.5.0`
+    a_core
+        .cmp(&b_core)
+        .then_with(|| match (a_pre_release, b_pre_release) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(a), Some(b)) => a
+                .split('.')
+                .zip(b.split('.'))
+                .map(|(a, b)| compare_identifiers(a, b))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| a.split('.').count().cmp(&b.split('.').count())),
+        })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeploymentError {
+    UnknownVersion(String),
+    AlreadyInstalled(String),
+    InProgress(String),
+    Requires(String),
+}
+
+impl std::fmt::Display for DeploymentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeploymentError::UnknownVersion(version) => write!(f, "no release '{version}'"),
+            DeploymentError::AlreadyInstalled(version) => {
+                write!(f, "version {version} is already installed")
+            }
+            DeploymentError::InProgress(version) => {
+                write!(f, "deployment of {version} is still in progress")
+            }
+            DeploymentError::Requires(version) => {
+                write!(
+                    f,
+                    "requires version {version} or newer to be installed first"
+                )
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeploymentStage {
+    Downloading,
+    Installing,
+    Restarting,
+}
+
+impl std::fmt::Display for DeploymentStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeploymentStage::Downloading => write!(f, "downloading"),
+            DeploymentStage::Installing => write!(f, "installing"),
+            DeploymentStage::Restarting => write!(f, "restarting"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeploymentOutcome {
+    Succeeded,
+    Failed(Failure),
+}
+
+impl std::fmt::Display for DeploymentOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeploymentOutcome::Succeeded => write!(f, "succeeded"),
+            DeploymentOutcome::Failed(failure) => write!(f, "failed with {failure}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Deployment {
+    pub release: Release,
+    pub stage: DeploymentStage,
+    elapsed: f32,
+}
+
+impl Deployment {
+    pub fn new(release: Release) -> Self {
+        Self {
+            release,
+            stage: DeploymentStage::Downloading,
+            elapsed: 0.,
+        }
+    }
+
+    pub fn progress(&self) -> f32 {
+        (self.elapsed / STAGE_SECONDS).min(1.)
+    }
+
+    pub fn advance(&mut self, delta: f32) -> bool {
+        self.elapsed += delta;
+
+        if self.elapsed < STAGE_SECONDS {
+            return false;
+        }
+
+        self.elapsed -= STAGE_SECONDS;
+        self.stage = match self.stage {
+            DeploymentStage::Downloading => DeploymentStage::Installing,
+            DeploymentStage::Installing => DeploymentStage::Restarting,
+            DeploymentStage::Restarting => return true,
+        };
+
+        false
+    }
+}
+
+impl std::fmt::Display for Deployment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "deploying {}: {} {:.0}%",
+            self.release.version,
+            self.stage,
+            self.progress() * 100.
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_are_compared_numerically() {
+        assert_eq!(compare_versions("2.10.0", "2.9.0"), Ordering::Greater);
+        assert_eq!(compare_versions("2.5.0", "2.5.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.9.9", "2.0.0"), Ordering::Less);
+    }
+
+    #[test]
+    fn pre_releases_come_before_the_release() {
+        assert_eq!(compare_versions("2.5.0-rc1", "2.5.0"), Ordering::Less);
+        assert_eq!(compare_versions("2.5.0", "2.5.0-rc1"), Ordering::Greater);
+        assert_eq!(compare_versions("2.5.0-rc1", "2.4.9"), Ordering::Greater);
+        assert_eq!(
+            compare_versions("2.5.0-rc.2", "2.5.0-rc.10"),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_versions("2.5.0-alpha", "2.5.0-beta"),
+            Ordering::Less
+        );
+        assert_eq!(compare_versions("2.5.0-rc", "2.5.0-rc.1"), Ordering::Less);
+        assert_eq!(compare_versions("2.5.0-1", "2.5.0-rc"), Ordering::Less);
+    }
+
+    #[test]
+    fn deployments_advance_a_stage_every_few_seconds() {
+        let mut deployment = Deployment::new(Release::new("2.0.0"));
+
+        assert!(!deployment.advance(STAGE_SECONDS / 2.));
+        assert_eq!(deployment.stage, DeploymentStage::Downloading);
+        assert_eq!(deployment.to_string(), "deploying 2.0.0: downloading 50%");
+
+        assert!(!deployment.advance(STAGE_SECONDS));
+        assert_eq!(deployment.stage, DeploymentStage::Installing);
+
+        assert!(!deployment.advance(STAGE_SECONDS));
+        assert_eq!(deployment.stage, DeploymentStage::Restarting);
+        assert!(deployment.advance(STAGE_SECONDS));
+    }
+}
@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+
+use super::{
+    database::Database,
+    process::ProcessTable,
+    release::{compare_versions, Deployment, DeploymentError, DeploymentOutcome, Failure, Release},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HostProperty {
+    Version,
+    Uptime,
+    Users,
+    Services,
+    DbSchemaVersion,
+    Disk,
+    Load,
+    Status,
+}
+
+impl HostProperty {
+    pub const ALL: [HostProperty; 8] = [
+        HostProperty::Version,
+        HostProperty::Uptime,
+        HostProperty::Users,
+        HostProperty::Services,
+        HostProperty::DbSchemaVersion,
+        HostProperty::Disk,
+        HostProperty::Load,
+        HostProperty::Status,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HostProperty::Version => "version",
+            HostProperty::Uptime => "uptime",
+            HostProperty::Users => "users",
+            HostProperty::Services => "services",
+            HostProperty::DbSchemaVersion => "db-schema-version",
+            HostProperty::Disk => "disk",
+            HostProperty::Load => "load",
+            HostProperty::Status => "status",
+        }
+    }
+}
+
+impl TryFrom<&str> for HostProperty {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        HostProperty::ALL
+            .into_iter()
+            .find(|property| property.name() == value)
+            .ok_or_else(|| format!("unknown property '{value}'"))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HostStatus {
+    #[default]
+    Healthy,
+    Failed(Failure),
+}
+
+impl std::fmt::Display for HostStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HostStatus::Healthy => write!(f, "healthy"),
+            HostStatus::Failed(failure) => write!(f, "{failure}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HostState {
+    pub version: String,
+    pub uptime: f64,
+    pub users: Vec<String>,
+    pub processes: ProcessTable,
+    pub database: Option<Database>,
+    pub disk_used: f32,
+    pub disk_total: f32,
+    pub load: f32,
+    pub status: HostStatus,
+    pub releases: Vec<Release>,
+    pub deployment: Option<Deployment>,
+}
+
+impl Default for HostState {
+    fn default() -> Self {
+        Self {
+            version: String::from("1.0.0"),
+            uptime: 0.,
+            users: Vec::new(),
+            processes: ProcessTable::default(),
+            database: None,
+            disk_used: 10.,
+            disk_total: 100.,
+            load: 0.1,
+            status: HostStatus::default(),
+            releases: Vec::new(),
+            deployment: None,
+        }
+    }
+}
+
+impl HostState {
+    pub fn tick(
+        &mut self,
+        delta: f32,
+        schema_versions: &HashMap<String, u32>,
+    ) -> Option<DeploymentOutcome> {
+        self.uptime += f64::from(delta);
+
+        let active = self.processes.active_count() as f32;
+        self.load = 0.1 + 0.2 * active + 0.15 * (self.uptime / 30.).sin().abs() as f32;
+        self.processes.tick(self.uptime);
+
+        let finished = self
+            .deployment
+            .as_mut()
+            .is_some_and(|deployment| deployment.advance(delta));
+
+        if !finished {
+            return None;
+        }
+
+        let release = self.deployment.take()?.release;
+
+        Some(self.finish_deployment(release, schema_versions))
+    }
+
+    pub fn deploy(&mut self, version: &str, force: bool) -> Result<&Deployment, DeploymentError> {
+        if let Some(deployment) = &self.deployment {
+            return Err(DeploymentError::InProgress(
+                deployment.release.version.clone(),
+            ));
+        }
+
+        if self.version == version {
+            return Err(DeploymentError::AlreadyInstalled(version.to_string()));
+        }
+
+        let release = self
+            .releases
+            .iter()
+            .find(|release| release.version == version)
+            .ok_or_else(|| DeploymentError::UnknownVersion(version.to_string()))?;
+
+        if let Some(requires) = &release.requires {
+            if !force && compare_versions(&self.version, requires).is_lt() {
+                return Err(DeploymentError::Requires(requires.clone()));
+            }
+        }
+
+        Ok(self.deployment.insert(Deployment::new(release.clone())))
+    }
+
+    fn schema_versions<'a>(
+        &'a self,
+        schema_versions: &'a HashMap<String, u32>,
+    ) -> impl Iterator<Item = u32> + 'a {
+        let remote = self
+            .processes
+            .services()
+            .filter(|service| service.release)
+            .flat_map(|service| &service.requires)
+            .filter_map(|dependency| dependency.split_once(':'))
+            .filter_map(|(hostname, _)| schema_versions.get(hostname).copied());
+
+        self.database
+            .iter()
+            .map(|database| database.schema_version)
+            .chain(remote)
+    }
+
+    fn finish_deployment(
+        &mut self,
+        release: Release,
+        schema_versions: &HashMap<String, u32>,
+    ) -> DeploymentOutcome {
+        let incompatible = release.schema_version.is_some_and(|required| {
+            self.schema_versions(schema_versions)
+                .any(|schema_version| schema_version < required)
+        });
+
+        self.version = release.version;
+
+        let failure = match release.failure {
+            Some(failure) => failure,
+            None if incompatible => Failure::CrashLoop,
+            None => {
+                self.status = HostStatus::Healthy;
+                self.processes.start_releases();
+                return DeploymentOutcome::Succeeded;
+            }
+        };
+
+        match failure {
+            Failure::CrashLoop => self.processes.fail_releases(),
+            Failure::DataLoss => {
+                self.disk_used *= 0.25;
+
+                if let Some(database) = &mut self.database {
+                    database.truncate_all();
+                }
+            }
+        }
+
+        self.status = HostStatus::Failed(failure);
+
+        DeploymentOutcome::Failed(failure)
+    }
+
+    pub fn crash_looping(&self) -> bool {
+        self.status == HostStatus::Failed(Failure::CrashLoop)
+    }
+
+    pub fn show(&self, property: HostProperty) -> Vec<String> {
+        match property {
+            HostProperty::Version => vec![self.version.clone()],
+            HostProperty::Uptime => {
+                let minutes = (self.uptime / 60.) as u32;
+                let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+
+                vec![format!("up {days} days, {hours:02}:{minutes:02}")]
+            }
+            HostProperty::Users => match self.users.is_empty() {
+                true => vec![String::from("no users logged in")],
+                false => self.users.clone(),
+            },
+            HostProperty::Services => self
+                .processes
+                .services()
+                .map(|service| format!("{:<16}{}", service.name, service.state))
+                .collect(),
+            HostProperty::DbSchemaVersion => match &self.database {
+                Some(database) => vec![database.schema_version.to_string()],
+                None => vec![String::from("no database on this host")],
+            },
+            HostProperty::Disk => vec![format!(
+                "{:.1}G used of {:.1}G ({:.0}%)",
+                self.disk_used,
+                self.disk_total,
+                self.disk_used / self.disk_total * 100.
+            )],
+            HostProperty::Load => vec![format!("{:.2}", self.load)],
+            HostProperty::Status => match &self.deployment {
+                Some(deployment) => vec![deployment.to_string()],
+                None => vec![self.status.to_string()],
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> HostState {
+        HostState {
+            releases: vec![
+                Release::new("1.1.0"),
+                Release {
+                    requires: Some(String::from("1.1.0")),
+                    schema_version: Some(4),
+                    ..Release::new("2.0.0")
+                },
+            ],
+            database: Some(Database::new(3)),
+            ..Default::default()
+        }
+    }
+
+    fn finish(state: &mut HostState) -> Option<DeploymentOutcome> {
+        (0..3)
+            .map(|_| state.tick(5., &HashMap::new()))
+            .last()
+            .flatten()
+    }
+
+    #[test]
+    fn ticks_advance_uptime_and_load() {
+        let mut state = HostState::default();
+
+        for _ in 0..120 {
+            state.tick(0.5, &HashMap::new());
+        }
+
+        assert_eq!(state.show(HostProperty::Uptime), ["up 0 days, 00:01"]);
+        assert!(state.load >= 0.1 && state.load <= 0.25);
+        assert_eq!(state.tick(0.5, &HashMap::new()), None);
+    }
+
+    #[test]
+    fn properties_are_parsed_by_name() {
+        assert_eq!(
+            HostProperty::try_from("db-schema-version"),
+            Ok(HostProperty::DbSchemaVersion)
+        );
+        assert_eq!(
+            HostProperty::try_from("nope"),
+            Err(String::from("unknown property 'nope'"))
+        );
+    }
+
+    #[test]
+    fn deployments_are_validated() {
+        let mut state = state();
+
+        assert_eq!(
+            state.deploy("9.9.9", false).map(|_| ()),
+            Err(DeploymentError::UnknownVersion(String::from("9.9.9")))
+        );
+        assert_eq!(
+            state.deploy("1.0.0", false).map(|_| ()),
+            Err(DeploymentError::AlreadyInstalled(String::from("1.0.0")))
+        );
+        assert_eq!(
+            state.deploy("2.0.0", false).map(|_| ()),
+            Err(DeploymentError::Requires(String::from("1.1.0")))
+        );
+        assert!(state.deploy("2.0.0", true).is_ok());
+        assert_eq!(
+            state.deploy("1.1.0", false).map(|_| ()),
+            Err(DeploymentError::InProgress(String::from("2.0.0")))
+        );
+    }
+
+    #[test]
+    fn ticks_finish_a_deployment_after_every_stage() {
+        let mut state = state();
+
+        state.deploy("1.1.0", false).unwrap();
+
+        assert_eq!(state.tick(5., &HashMap::new()), None);
+        assert_eq!(state.tick(5., &HashMap::new()), None);
+        assert_eq!(
+            state.show(HostProperty::Status),
+            ["deploying 1.1.0: restarting 0%"]
+        );
+        assert_eq!(
+            state.tick(5., &HashMap::new()),
+            Some(DeploymentOutcome::Succeeded)
+        );
+        assert_eq!(state.version, "1.1.0");
+        assert_eq!(state.status, HostStatus::Healthy);
+        assert!(state.deployment.is_none());
+        assert_eq!(state.uptime, 15.);
+    }
+
+    #[test]
+    fn an_incompatible_schema_crash_loops() {
+        let mut state = state();
+
+        state.deploy("2.0.0", true).unwrap();
+
+        assert_eq!(
+            finish(&mut state),
+            Some(DeploymentOutcome::Failed(Failure::CrashLoop))
+        );
+        assert_eq!(state.version, "2.0.0");
+        assert!(state.crash_looping());
+    }
+
+    #[test]
+    fn a_migrated_schema_is_compatible() {
+        let mut state = state();
+
+        state.database = Some(Database::new(4));
+        state.deploy("2.0.0", true).unwrap();
+
+        assert_eq!(finish(&mut state), Some(DeploymentOutcome::Succeeded));
+    }
+}
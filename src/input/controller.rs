@@ -1,17 +1,24 @@
 use bevy::prelude::*;
 
-use super::model::{MovementDirection, HasInput};
+use super::model::{Facing, HasInput, MovementDirection};
 
-pub fn handle_input(keys: Res<Input<KeyCode>>, mut direction: Query<&mut MovementDirection, With<HasInput>>) {
+pub fn handle_input(
+    keys: Res<Input<KeyCode>>,
+    mut direction: Query<(&mut MovementDirection, &mut Facing), With<HasInput>>,
+) {
     trace!("handle_input");
 
     let movement = get_movement_direction(keys);
     trace!("{:?}", movement);
 
-    for mut direction in direction.iter_mut() {
+    for (mut direction, mut facing) in direction.iter_mut() {
         trace!("{:?}", direction);
 
         *direction = movement;
+
+        if movement != MovementDirection::None {
+            *facing = Facing(movement.vector());
+        }
     }
 }
 
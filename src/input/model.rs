@@ -3,7 +3,7 @@ use bevy::prelude::*;
 #[derive(Component, Default, Debug, Clone, Copy)]
 pub struct HasInput;
 
-#[derive(Component, Default, Debug, Clone, Copy)]
+#[derive(Component, Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MovementDirection {
     Up,
     Down,
@@ -12,3 +12,33 @@ pub enum MovementDirection {
     #[default]
     None,
 }
+
+impl MovementDirection {
+    pub fn vector(&self) -> Vec2 {
+        match self {
+            MovementDirection::Up => Vec2::Y,
+            MovementDirection::Down => Vec2::NEG_Y,
+            MovementDirection::Left => Vec2::NEG_X,
+            MovementDirection::Right => Vec2::X,
+            MovementDirection::None => Vec2::ZERO,
+        }
+    }
+}
+
+#[derive(Component, Debug, Deref, Clone, Copy)]
+pub struct Facing(pub Vec2);
+
+impl Default for Facing {
+    fn default() -> Self {
+        Self(Vec2::NEG_Y)
+    }
+}
+
+impl Facing {
+    pub fn faces(&self, from: Vec3, to: Vec3, distance: f32) -> bool {
+        let offset = (to - from).truncate();
+
+        offset.length() <= distance
+            && (offset.length() < 1. || offset.normalize().dot(self.0) > 0.7)
+    }
+}
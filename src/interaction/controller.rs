@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+use super::model::{Focus, Interactable, InteractionEvent};
+use crate::{
+    input::model::{Facing, HasInput},
+    item::model::Inventory,
+};
+
+pub fn focus(
+    players: Query<(&Transform, &Facing), With<HasInput>>,
+    interactables: Query<(Entity, &Transform, &Interactable)>,
+    mut focus: ResMut<Focus>,
+) {
+    let focused = players.iter().find_map(|(player, facing)| {
+        interactables
+            .iter()
+            .filter(|(_, transform, interactable)| {
+                facing.faces(
+                    player.translation,
+                    transform.translation,
+                    interactable.radius,
+                )
+            })
+            .min_by(|(_, a, _), (_, b, _)| {
+                let a = a.translation.distance(player.translation);
+                let b = b.translation.distance(player.translation);
+
+                a.total_cmp(&b)
+            })
+            .map(|(entity, ..)| entity)
+    });
+
+    if **focus != focused {
+        **focus = focused;
+    }
+}
+
+pub fn unfocus(mut focus: ResMut<Focus>) {
+    **focus = None;
+}
+
+pub fn interact(
+    keys: Res<Input<KeyCode>>,
+    focus: Res<Focus>,
+    players: Query<(Entity, &Inventory), With<HasInput>>,
+    interactables: Query<&Interactable>,
+    mut interactions: EventWriter<InteractionEvent>,
+) {
+    if !keys.just_pressed(KeyCode::E) {
+        return;
+    }
+
+    let Some(target) = **focus else {
+        return;
+    };
+
+    let Ok(interactable) = interactables.get(target) else {
+        return;
+    };
+
+    for (actor, inventory) in players.iter() {
+        if let Some(card) = &interactable.requires {
+            if !inventory.has(card) {
+                info!("{} requires the '{card}' card", interactable.prompt);
+                continue;
+            }
+        }
+
+        interactions.send(InteractionEvent { actor, target });
+    }
+}
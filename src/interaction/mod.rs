@@ -0,0 +1,26 @@
+use self::{
+    controller::{focus, interact, unfocus},
+    model::{Focus, InteractionEvent},
+    view::{show_hint, startup},
+};
+use crate::game::model::GameState;
+use bevy::prelude::*;
+
+pub mod controller;
+pub mod model;
+pub mod view;
+
+const INTERACTION_RADIUS: f32 = 24.;
+
+pub struct InteractionPlugin;
+
+impl Plugin for InteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Focus>()
+            .add_event::<InteractionEvent>()
+            .add_startup_system(startup)
+            .add_systems((focus, interact.after(focus)).in_set(OnUpdate(GameState::Movement)))
+            .add_system(unfocus.in_schedule(OnExit(GameState::Movement)))
+            .add_system(show_hint);
+    }
+}
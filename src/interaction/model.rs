@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+use super::INTERACTION_RADIUS;
+
+#[derive(Component, Clone, Debug)]
+pub struct Interactable {
+    pub radius: f32,
+    pub prompt: String,
+    pub requires: Option<String>,
+}
+
+impl Interactable {
+    pub fn new(prompt: impl Into<String>) -> Self {
+        Self {
+            radius: INTERACTION_RADIUS,
+            prompt: prompt.into(),
+            requires: None,
+        }
+    }
+
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    pub fn requires(mut self, card: &str) -> Self {
+        self.requires = Some(String::from(card));
+        self
+    }
+}
+
+#[derive(Resource, Default, Debug, Deref, DerefMut)]
+pub struct Focus(pub Option<Entity>);
+
+#[derive(Debug)]
+pub struct InteractionEvent {
+    pub actor: Entity,
+    pub target: Entity,
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+use super::model::{Focus, Interactable};
+use crate::{input::model::HasInput, item::model::Inventory};
+
+#[derive(Component, Default)]
+pub struct InteractionHint;
+
+pub fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load("fonts/fira-code/regular.ttf"),
+                font_size: 16.0,
+                color: Color::WHITE,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                bottom: Val::Px(16.0),
+                left: Val::Px(16.0),
+                ..default()
+            },
+            ..default()
+        }),
+        InteractionHint,
+    ));
+}
+
+pub fn show_hint(
+    focus: Res<Focus>,
+    interactables: Query<&Interactable>,
+    changed: Query<(), Changed<Interactable>>,
+    inventories: Query<&Inventory, With<HasInput>>,
+    mut hints: Query<&mut Text, With<InteractionHint>>,
+) {
+    if !focus.is_changed() && !focus.is_some_and(|target| changed.contains(target)) {
+        return;
+    }
+
+    let hint = match focus.and_then(|target| interactables.get(target).ok()) {
+        None => String::new(),
+        Some(Interactable {
+            requires: Some(card),
+            prompt,
+            ..
+        }) if !inventories.iter().any(|inventory| inventory.has(card)) => {
+            format!("{prompt} (requires the '{card}' card)")
+        }
+        Some(interactable) => format!("press E to {}", interactable.prompt),
+    };
+
+    for mut text in hints.iter_mut() {
+        text.sections[0].value = hint.clone();
+    }
+}
@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+use super::model::{AccessCard, Inventory};
+use crate::{
+    input::model::HasInput,
+    interaction::model::InteractionEvent,
+    shell::{model::Selected, session::ShellSession},
+};
+
+pub fn pick_up_cards(
+    mut commands: Commands,
+    mut interactions: EventReader<InteractionEvent>,
+    mut inventories: Query<&mut Inventory>,
+    cards: Query<&AccessCard>,
+) {
+    for event in interactions.iter() {
+        let (Ok(card), Ok(mut inventory)) =
+            (cards.get(event.target), inventories.get_mut(event.actor))
+        else {
+            continue;
+        };
+
+        info!("picked up access card '{}'", card.name);
+
+        inventory.add(card.clone());
+        commands.entity(event.target).despawn();
+    }
+}
+
+pub fn cycle_held_card(
+    keys: Res<Input<KeyCode>>,
+    mut inventories: Query<&mut Inventory, With<HasInput>>,
+) {
+    if !keys.just_pressed(KeyCode::C) {
+        return;
+    }
+
+    for mut inventory in inventories.iter_mut() {
+        inventory.cycle();
+
+        if let Some(card) = inventory.held() {
+            info!("holding access card '{}'", card.name);
+        }
+    }
+}
+
+pub fn insert_card(
+    inventories: Query<&Inventory, With<HasInput>>,
+    mut shells: Query<&mut ShellSession, With<Selected>>,
+) {
+    let card = inventories
+        .iter()
+        .find_map(|inventory| inventory.held())
+        .cloned();
+
+    for mut session in shells.iter_mut() {
+        session.card = card.clone();
+    }
+}
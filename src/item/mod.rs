@@ -0,0 +1,16 @@
+use self::controller::{cycle_held_card, insert_card, pick_up_cards};
+use crate::game::model::GameState;
+use bevy::prelude::*;
+
+pub mod controller;
+pub mod model;
+pub mod view;
+
+pub struct ItemPlugin;
+
+impl Plugin for ItemPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems((pick_up_cards, cycle_held_card).in_set(OnUpdate(GameState::Movement)))
+            .add_system(insert_card.in_schedule(OnEnter(GameState::Shell)));
+    }
+}
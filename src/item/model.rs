@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+
+use crate::{interaction::model::Interactable, shell::model::AccessLevel};
+
+#[derive(Component, Clone, Debug)]
+pub struct AccessCard {
+    pub name: String,
+    pub clearance: AccessLevel,
+    pub hosts: Vec<String>,
+}
+
+impl AccessCard {
+    pub fn grants(&self, hostname: &str) -> bool {
+        self.hosts.is_empty() || self.hosts.iter().any(|host| host == hostname)
+    }
+}
+
+#[derive(Component, Default, Debug)]
+pub struct Inventory {
+    pub cards: Vec<AccessCard>,
+    pub held: Option<usize>,
+}
+
+impl Inventory {
+    pub fn add(&mut self, card: AccessCard) {
+        self.cards.push(card);
+
+        if self.held.is_none() {
+            self.held = Some(self.cards.len() - 1);
+        }
+    }
+
+    pub fn has(&self, name: &str) -> bool {
+        self.cards.iter().any(|card| card.name == name)
+    }
+
+    pub fn held(&self) -> Option<&AccessCard> {
+        self.cards.get(self.held?)
+    }
+
+    pub fn cycle(&mut self) {
+        if self.cards.is_empty() {
+            return;
+        }
+
+        self.held = Some(self.held.map_or(0, |held| (held + 1) % self.cards.len()));
+    }
+}
+
+#[derive(Bundle)]
+pub struct AccessCardBundle {
+    pub sprite_bundle: SpriteBundle,
+    pub interactable: Interactable,
+    pub card: AccessCard,
+}
+
+impl AccessCardBundle {
+    pub fn new(card: AccessCard, x: f32, y: f32) -> Self {
+        Self {
+            sprite_bundle: SpriteBundle {
+                sprite: Sprite {
+                    color: Color::GOLD,
+                    custom_size: Some(Vec2::new(10., 6.)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(x, y, 0.),
+                ..default()
+            },
+            interactable: Interactable::new(format!("pick up the '{}' card", card.name)),
+            card,
+        }
+    }
+}
@@ -1,5 +1,8 @@
 pub mod game;
+pub mod host;
 pub mod input;
+pub mod interaction;
+pub mod item;
 pub mod object;
 pub mod physics;
 pub mod shell;
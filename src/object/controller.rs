@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+
+use super::{
+    level::Level,
+    model::{CurrentLevel, CurrentRoom, Door, Npc, SpeechBubble},
+};
+use crate::{
+    host::{model::ServiceEvent, process::ServiceState},
+    input::model::HasInput,
+    interaction::model::{Interactable, InteractionEvent},
+    physics::{controller::shape, model::Collider},
+};
+
+pub fn enter_room(
+    levels: Res<Assets<Level>>,
+    current_level: Res<CurrentLevel>,
+    players: Query<&Transform, With<HasInput>>,
+    mut current_room: ResMut<CurrentRoom>,
+) {
+    let Some(level) = levels.get(&current_level) else {
+        return;
+    };
+
+    for transform in players.iter() {
+        let Some(room) = level.room(transform.translation.truncate()) else {
+            continue;
+        };
+
+        if current_room.as_deref() != Some(&room.name) {
+            *current_room = CurrentRoom(Some(room.name.clone()));
+        }
+    }
+}
+
+pub fn react_to_outages(
+    mut service_events: EventReader<ServiceEvent>,
+    npcs: Query<&Children, With<Npc>>,
+    mut bubbles: Query<(&mut Text, &mut SpeechBubble)>,
+) {
+    for event in service_events.iter() {
+        if event.state != ServiceState::Failed {
+            continue;
+        }
+
+        for (idx, children) in npcs.iter().enumerate() {
+            let line = match idx % 3 {
+                0 => format!("is {} on {} down?", event.service, event.hostname),
+                1 => format!("who touched {}?", event.hostname),
+                _ => format!("{} is failing again...", event.service),
+            };
+
+            let mut bubbles = bubbles.iter_many_mut(children);
+
+            while let Some((mut text, mut bubble)) = bubbles.fetch_next() {
+                text.sections[0].value = line.clone();
+                bubble.reset();
+            }
+        }
+    }
+}
+
+pub fn toggle_doors(
+    mut commands: Commands,
+    mut interactions: EventReader<InteractionEvent>,
+    images: Res<Assets<Image>>,
+    mut doors: Query<(
+        &mut Door,
+        &mut Interactable,
+        &mut Sprite,
+        &Handle<Image>,
+        &Transform,
+    )>,
+    players: Query<(Entity, &Transform, &Collider), With<HasInput>>,
+    sprites: Query<(&Sprite, &Handle<Image>), Without<Door>>,
+) {
+    for event in interactions.iter() {
+        let Ok((mut door, mut interactable, mut sprite, texture, transform)) =
+            doors.get_mut(event.target)
+        else {
+            continue;
+        };
+
+        let door_shape = shape(
+            &images,
+            Some((&sprite, texture)),
+            transform,
+            &Collider::default(),
+        );
+        let blocked = door_shape.is_some_and(|door_shape| {
+            players.iter().any(|(player, player_transform, collider)| {
+                shape(
+                    &images,
+                    sprites.get(player).ok(),
+                    player_transform,
+                    collider,
+                )
+                .is_some_and(|player_shape| {
+                    player_shape.intersects(
+                        player_transform.translation.truncate(),
+                        door_shape,
+                        transform.translation.truncate(),
+                    )
+                })
+            })
+        });
+
+        if door.open && blocked {
+            continue;
+        }
+
+        door.open = !door.open;
+
+        if door.open {
+            commands.entity(event.target).remove::<Collider>();
+            interactable.prompt = String::from("close the door");
+            sprite.color.set_a(0.3);
+        } else {
+            commands.entity(event.target).insert(Collider::default());
+            interactable.prompt = String::from("open the door");
+            sprite.color.set_a(1.);
+        }
+    }
+}
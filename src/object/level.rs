@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+use bevy::{
+    asset::{AssetLoader, Error, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+use super::TILE_SIZE;
+use crate::shell::model::AccessLevel;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LevelError {
+    UnknownTile {
+        symbol: char,
+        row: usize,
+        column: usize,
+    },
+    RoomOutsideMap(String),
+}
+
+impl std::fmt::Display for LevelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LevelError::UnknownTile {
+                symbol,
+                row,
+                column,
+            } => write!(f, "unknown tile '{symbol}' at row {row}, column {column}"),
+            LevelError::RoomOutsideMap(room) => write!(f, "room '{room}' does not fit on the map"),
+        }
+    }
+}
+
+impl std::error::Error for LevelError {}
+
+#[derive(Clone, Debug, Deserialize)]
+pub enum Tile {
+    Wall,
+    Floor,
+    Player,
+    Terminal(String),
+    Door {
+        requires: Option<String>,
+    },
+    Npc(String),
+    Card {
+        name: String,
+        clearance: AccessLevel,
+        hosts: Vec<String>,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Room {
+    pub name: String,
+    pub column: usize,
+    pub row: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+#[derive(Debug, Deserialize, TypeUuid)]
+#[uuid = "5d3c1c1e-8a0f-4c6e-9a59-2f1f8e7b6a41"]
+pub struct Level {
+    pub map: Vec<String>,
+    #[serde(default)]
+    pub legend: HashMap<char, Tile>,
+    #[serde(default)]
+    pub rooms: Vec<Room>,
+}
+
+impl Level {
+    pub fn tile(&self, symbol: char) -> Option<Tile> {
+        match (self.legend.get(&symbol), symbol) {
+            (Some(tile), _) => Some(tile.clone()),
+            (None, '#') => Some(Tile::Wall),
+            (None, '.') => Some(Tile::Floor),
+            (None, '@') => Some(Tile::Player),
+            (None, _) => None,
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        let columns = self
+            .map
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        Vec2::new(columns as f32, self.map.len() as f32) * TILE_SIZE
+    }
+
+    pub fn bounds(&self) -> Rect {
+        Rect::from_center_size(Vec2::ZERO, self.size())
+    }
+
+    pub fn area(&self, room: &Room) -> Rect {
+        let top_left = Vec2::new(-self.size().x, self.size().y) / 2.;
+        let min = top_left
+            + Vec2::new(room.column as f32, -((room.row + room.height) as f32)) * TILE_SIZE;
+        let size = Vec2::new(room.width as f32, room.height as f32) * TILE_SIZE;
+
+        Rect::from_corners(min, min + size)
+    }
+
+    pub fn room(&self, position: Vec2) -> Option<&Room> {
+        self.rooms
+            .iter()
+            .find(|room| self.area(room).contains(position))
+    }
+
+    pub fn validate(&self) -> Result<(), LevelError> {
+        for (row, symbols) in self.map.iter().enumerate() {
+            for (column, symbol) in symbols.chars().enumerate() {
+                if symbol != ' ' && self.tile(symbol).is_none() {
+                    return Err(LevelError::UnknownTile {
+                        symbol,
+                        row: row + 1,
+                        column: column + 1,
+                    });
+                }
+            }
+        }
+
+        let bounds = self.bounds();
+
+        for room in &self.rooms {
+            let area = self.area(room);
+
+            if area.is_empty() || bounds.intersect(area) != area {
+                return Err(LevelError::RoomOutsideMap(room.name.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn tiles(&self) -> impl Iterator<Item = (Vec2, Tile)> + '_ {
+        let origin = Vec2::new(TILE_SIZE - self.size().x, self.size().y - TILE_SIZE) / 2.;
+
+        self.map.iter().enumerate().flat_map(move |(row, symbols)| {
+            symbols
+                .chars()
+                .enumerate()
+                .filter_map(move |(column, symbol)| {
+                    let offset = Vec2::new(column as f32, -(row as f32)) * TILE_SIZE;
+
+                    self.tile(symbol).map(|tile| (origin + offset, tile))
+                })
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let level = ron::de::from_bytes::<Level>(bytes)?;
+
+            level.validate()?;
+
+            load_context.set_default_asset(LoadedAsset::new(level));
+
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(map: &[&str], legend: &[(char, Tile)], rooms: &[Room]) -> Level {
+        Level {
+            map: map.iter().map(|row| row.to_string()).collect(),
+            legend: legend.iter().cloned().collect(),
+            rooms: rooms.to_vec(),
+        }
+    }
+
+    fn room(name: &str, column: usize, row: usize, width: usize, height: usize) -> Room {
+        Room {
+            name: name.to_string(),
+            column,
+            row,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn valid_levels_place_their_tiles() {
+        let level = level(
+            &["####", "#@T#", "####"],
+            &[('T', Tile::Terminal(String::from("test01")))],
+            &[room("office", 1, 1, 2, 1)],
+        );
+
+        assert!(level.validate().is_ok());
+        assert_eq!(level.size(), Vec2::new(4., 3.) * TILE_SIZE);
+        assert_eq!(level.tiles().count(), 12);
+        assert!(matches!(
+            level.tiles().nth(6),
+            Some((position, Tile::Terminal(hostname)))
+                if position == Vec2::new(TILE_SIZE / 2., 0.) && hostname == "test01"
+        ));
+        assert_eq!(
+            level.room(Vec2::ZERO).map(|room| room.name.as_str()),
+            Some("office")
+        );
+        assert!(level.room(Vec2::new(TILE_SIZE * 1.5, 0.)).is_none());
+    }
+
+    #[test]
+    fn unknown_legend_characters_are_rejected() {
+        let level = level(&["#.#", "#?#"], &[], &[]);
+
+        assert_eq!(
+            level.validate(),
+            Err(LevelError::UnknownTile {
+                symbol: '?',
+                row: 2,
+                column: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn rooms_must_fit_on_the_map() {
+        let level = level(&["###", "#.#", "###"], &[], &[room("annex", 2, 1, 2, 1)]);
+
+        assert_eq!(
+            level.validate(),
+            Err(LevelError::RoomOutsideMap(String::from("annex")))
+        );
+    }
+
+    #[test]
+    fn levels_are_read_from_ron() {
+        let level = ron::de::from_str::<Level>("(map: [\"@\"])").unwrap();
+
+        assert!(matches!(level.tile('@'), Some(Tile::Player)));
+        assert!(level.rooms.is_empty());
+    }
+}
@@ -1,67 +1,125 @@
+use self::{
+    controller::{enter_room, react_to_outages, toggle_doors},
+    level::{Level, LevelLoader, Tile},
+    model::{CurrentLevel, CurrentRoom, Door, LevelEntity, Npc, SpeechBubble, Terminal},
+    view::{show_room, show_speech, spawn_room_banner},
+};
 use crate::{
-    input::model::{HasInput, MovementDirection},
-    physics::model::{Collider, Speed}, game::{WINDOW_WIDTH, WINDOW_HEIGHT},
+    input::model::{Facing, HasInput, MovementDirection},
+    interaction::model::Interactable,
+    item::model::{AccessCard, AccessCardBundle, Inventory},
+    physics::model::{Collider, Speed},
 };
 use bevy::prelude::*;
 
 pub mod controller;
+pub mod level;
 pub mod model;
 pub mod view;
 
+pub const TILE_SIZE: f32 = 16.;
+const ROOM_BANNER_SECONDS: f32 = 2.;
+const SPEECH_SECONDS: f32 = 4.;
+
 pub struct ObjectsPlugin;
 
 impl Plugin for ObjectsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_startup_system(startup);
+        app.add_asset::<Level>()
+            .init_asset_loader::<LevelLoader>()
+            .init_resource::<CurrentRoom>()
+            .add_startup_system(startup)
+            .add_startup_system(spawn_room_banner)
+            .add_systems((
+                spawn_level,
+                toggle_doors,
+                enter_room,
+                show_room,
+                react_to_outages,
+                show_speech,
+            ));
     }
 }
 
 fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.spawn(PlayerBundle {
-        sprite_bundle: SpriteBundle {
-            texture: asset_server.load("sprites/player.png"),
-            transform: Transform::from_xyz(160., 0., 0.),
-            ..default()
-        },
-        ..default()
-    });
-
-    let mut x = 0.;
-    while x <= WINDOW_WIDTH {
-        commands.spawn(WallBundle::new(
-            &asset_server,
-            snap_number(x - WINDOW_WIDTH / 2.),
-            snap_number(WINDOW_HEIGHT / 2. + 16.),
-            0.,
-        ));
-        commands.spawn(WallBundle::new(
-            &asset_server,
-            snap_number(x - WINDOW_WIDTH / 2.),
-            snap_number(-WINDOW_HEIGHT / 2.),
-            0.,
-        ));
-        x += 16.;
-    }
+    commands.insert_resource(CurrentLevel(asset_server.load("levels/office.level.ron")));
+}
 
-    let mut y = 0.;
-    while y <= WINDOW_WIDTH {
-        commands.spawn(WallBundle::new(
-            &asset_server,
-            snap_number(-WINDOW_WIDTH / 2.),
-            snap_number(WINDOW_HEIGHT / 2. - y),
-            0.,
-        ));
-        commands.spawn(WallBundle::new(
-            &asset_server,
-            snap_number(WINDOW_WIDTH / 2.),
-            snap_number(WINDOW_HEIGHT / 2. - y),
-            0.,
-        ));
-        y += 16.;
-    }
+fn spawn_level(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut level_events: EventReader<AssetEvent<Level>>,
+    levels: Res<Assets<Level>>,
+    current_level: Res<CurrentLevel>,
+    spawned: Query<Entity, With<LevelEntity>>,
+    players: Query<&Inventory, With<HasInput>>,
+) {
+    for event in level_events.iter() {
+        let (AssetEvent::Created { handle } | AssetEvent::Modified { handle }) = event else {
+            continue;
+        };
+
+        let Some(level) = levels.get(handle).filter(|_| *handle == **current_level) else {
+            continue;
+        };
+
+        for entity in spawned.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        let mut has_player = !players.is_empty();
 
-    commands.spawn(WallBundle::new(&asset_server, 64., 32., 0.));
-    commands.spawn(WallBundle::new(&asset_server, 80., 32., 0.));
+        for (Vec2 { x, y }, tile) in level.tiles() {
+            if !matches!(tile, Tile::Wall) {
+                commands.spawn((FloorBundle::new(x, y), LevelEntity));
+            }
+
+            match tile {
+                Tile::Wall => {
+                    commands.spawn((WallBundle::new(&asset_server, x, y, 0.), LevelEntity));
+                }
+                Tile::Floor => (),
+                Tile::Player if has_player => (),
+                Tile::Player => {
+                    has_player = true;
+                    commands.spawn(PlayerBundle::new(&asset_server, x, y));
+                }
+                Tile::Terminal(hostname) => {
+                    commands.spawn((TerminalBundle::new(&hostname, x, y), LevelEntity));
+                }
+                Tile::Door { requires } => {
+                    commands.spawn((DoorBundle::new(requires.as_deref(), x, y), LevelEntity));
+                }
+                Tile::Npc(name) => {
+                    commands
+                        .spawn((NpcBundle::new(&asset_server, &name, x, y), LevelEntity))
+                        .with_children(|parent| {
+                            parent.spawn(SpeechBubbleBundle::new(&asset_server));
+                        });
+                }
+                // A hot reload keeps the player, so cards they already picked up stay picked up
+                Tile::Card { name, .. } if players.iter().any(|player| player.has(&name)) => (),
+                Tile::Card {
+                    name,
+                    clearance,
+                    hosts,
+                } => {
+                    commands.spawn((
+                        AccessCardBundle::new(
+                            AccessCard {
+                                name,
+                                clearance,
+                                hosts,
+                            },
+                            x,
+                            y,
+                        ),
+                        LevelEntity,
+                    ));
+                }
+            }
+        }
+    }
 }
 
 #[derive(Bundle, Default)]
@@ -70,7 +128,43 @@ struct PlayerBundle {
     speed: Speed,
     collider: Collider,
     direction: MovementDirection,
+    facing: Facing,
     has_input: HasInput,
+    inventory: Inventory,
+}
+
+impl PlayerBundle {
+    fn new(asset_server: &Res<AssetServer>, x: f32, y: f32) -> PlayerBundle {
+        PlayerBundle {
+            sprite_bundle: SpriteBundle {
+                texture: asset_server.load("sprites/player.png"),
+                transform: Transform::from_xyz(x, y, 0.),
+                ..default()
+            },
+            ..default()
+        }
+    }
+}
+
+#[derive(Bundle)]
+struct FloorBundle {
+    sprite_bundle: SpriteBundle,
+}
+
+impl FloorBundle {
+    fn new(x: f32, y: f32) -> FloorBundle {
+        FloorBundle {
+            sprite_bundle: SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(0.16, 0.16, 0.18),
+                    custom_size: Some(Vec2::splat(TILE_SIZE)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(x, y, -1.),
+                ..default()
+            },
+        }
+    }
 }
 
 #[derive(Bundle, Default)]
@@ -92,6 +186,119 @@ impl WallBundle {
     }
 }
 
-fn snap_number(number: f32) -> f32 {
-    (number / 16.).floor() * 16.
+#[derive(Bundle)]
+struct TerminalBundle {
+    sprite_bundle: SpriteBundle,
+    collider: Collider,
+    interactable: Interactable,
+    terminal: Terminal,
+}
+
+impl TerminalBundle {
+    fn new(hostname: &str, x: f32, y: f32) -> TerminalBundle {
+        TerminalBundle {
+            sprite_bundle: SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(0.2, 0.6, 0.3),
+                    custom_size: Some(Vec2::new(16., 16.)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(x, y, 0.),
+                ..default()
+            },
+            collider: Collider::default(),
+            interactable: Interactable::new(format!("use the terminal {hostname}")),
+            terminal: Terminal {
+                hostname: String::from(hostname),
+            },
+        }
+    }
+}
+
+#[derive(Bundle)]
+struct DoorBundle {
+    sprite_bundle: SpriteBundle,
+    collider: Collider,
+    interactable: Interactable,
+    door: Door,
+}
+
+impl DoorBundle {
+    fn new(requires: Option<&str>, x: f32, y: f32) -> DoorBundle {
+        let interactable = Interactable::new("open the door");
+
+        DoorBundle {
+            sprite_bundle: SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(0.45, 0.3, 0.15),
+                    custom_size: Some(Vec2::splat(TILE_SIZE)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(x, y, 0.),
+                ..default()
+            },
+            collider: Collider::default(),
+            interactable: match requires {
+                Some(card) => interactable.requires(card),
+                None => interactable,
+            },
+            door: Door::default(),
+        }
+    }
+}
+
+#[derive(Bundle)]
+struct NpcBundle {
+    sprite_bundle: SpriteBundle,
+    collider: Collider,
+    npc: Npc,
+}
+
+impl NpcBundle {
+    fn new(asset_server: &Res<AssetServer>, name: &str, x: f32, y: f32) -> NpcBundle {
+        NpcBundle {
+            sprite_bundle: SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(TILE_SIZE)),
+                    ..default()
+                },
+                texture: asset_server.load("sprites/duck.png"),
+                transform: Transform::from_xyz(x, y, 0.),
+                ..default()
+            },
+            collider: Collider::circle(TILE_SIZE / 2.),
+            npc: Npc {
+                name: String::from(name),
+            },
+        }
+    }
+}
+
+#[derive(Bundle)]
+struct SpeechBubbleBundle {
+    text_bundle: Text2dBundle,
+    speech_bubble: SpeechBubble,
+}
+
+impl SpeechBubbleBundle {
+    fn new(asset_server: &Res<AssetServer>) -> SpeechBubbleBundle {
+        let mut timer = Timer::from_seconds(SPEECH_SECONDS, TimerMode::Once);
+        timer.tick(timer.duration());
+
+        SpeechBubbleBundle {
+            text_bundle: Text2dBundle {
+                text: Text::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load("fonts/fira-code/regular.ttf"),
+                        font_size: 12.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                transform: Transform::from_xyz(0., TILE_SIZE, 1.),
+                ..default()
+            },
+            speech_bubble: SpeechBubble(timer),
+        }
+    }
 }
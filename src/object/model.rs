@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+
+use super::level::Level;
+
+#[derive(Component, Clone, Debug)]
+pub struct Terminal {
+    pub hostname: String,
+}
+
+#[derive(Component, Default, Clone, Copy, Debug)]
+pub struct Door {
+    pub open: bool,
+}
+
+#[derive(Component, Clone, Debug)]
+pub struct Npc {
+    pub name: String,
+}
+
+#[derive(Component, Deref, DerefMut)]
+pub struct SpeechBubble(pub Timer);
+
+#[derive(Component, Default, Debug)]
+pub struct LevelEntity;
+
+#[derive(Resource, Debug, Deref)]
+pub struct CurrentLevel(pub Handle<Level>);
+
+#[derive(Resource, Default, Debug, Deref)]
+pub struct CurrentRoom(pub Option<String>);
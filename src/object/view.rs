@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use super::{
+    model::{CurrentRoom, SpeechBubble},
+    ROOM_BANNER_SECONDS,
+};
+
+#[derive(Component, Deref, DerefMut)]
+pub struct RoomBanner(Timer);
+
+pub fn spawn_room_banner(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let mut timer = Timer::from_seconds(ROOM_BANNER_SECONDS, TimerMode::Once);
+    timer.tick(timer.duration());
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(16.0),
+                    ..default()
+                },
+                size: Size::width(Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load("fonts/fira-code/regular.ttf"),
+                        font_size: 24.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                RoomBanner(timer),
+            ));
+        });
+}
+
+pub fn show_room(
+    time: Res<Time>,
+    current_room: Res<CurrentRoom>,
+    mut banners: Query<(&mut Text, &mut RoomBanner)>,
+) {
+    for (mut text, mut banner) in banners.iter_mut() {
+        if let (true, Some(room)) = (current_room.is_changed(), &**current_room) {
+            text.sections[0].value = room.clone();
+            banner.reset();
+        }
+
+        banner.tick(time.delta());
+        text.sections[0].style.color.set_a(banner.percent_left());
+    }
+}
+
+pub fn show_speech(time: Res<Time>, mut bubbles: Query<(&mut Text, &mut SpeechBubble)>) {
+    for (mut text, mut bubble) in bubbles.iter_mut() {
+        bubble.tick(time.delta());
+        text.sections[0].style.color.set_a(bubble.percent_left());
+    }
+}
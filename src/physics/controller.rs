@@ -1,6 +1,6 @@
-use super::model::{Collider, CollisionEvent, Speed};
+use super::model::{Collider, CollisionEvent, Shape, Speed};
 use crate::input::model::MovementDirection;
-use bevy::{prelude::*, sprite::collide_aabb::collide};
+use bevy::prelude::*;
 
 pub fn apply_movement(mut query: Query<(&mut Transform, &MovementDirection, &Speed)>) {
     trace!("apply_movement");
@@ -19,38 +19,52 @@ pub fn apply_movement(mut query: Query<(&mut Transform, &MovementDirection, &Spe
 }
 
 pub fn check_for_collisions(
-    mut me: Query<(Entity, &Transform, &mut MovementDirection, &Speed), With<Collider>>,
-    you: Query<(Entity, &Transform), With<Collider>>,
+    images: Res<Assets<Image>>,
+    sprites: Query<(&Sprite, &Handle<Image>)>,
+    mut me: Query<(Entity, &mut MovementDirection, &Speed), With<Collider>>,
+    you: Query<(Entity, &Transform, &Collider)>,
     mut collision_events: EventWriter<CollisionEvent>,
 ) {
-    for (my_entity, my_transform, mut my_direction, my_speed) in me.iter_mut() {
-        for (your_entity, your_transform) in you.iter() {
+    for (my_entity, mut my_direction, my_speed) in me.iter_mut() {
+        let Ok((_, my_transform, my_collider)) = you.get(my_entity) else {
+            continue;
+        };
+
+        let Some(my_shape) = shape(
+            &images,
+            sprites.get(my_entity).ok(),
+            my_transform,
+            my_collider,
+        ) else {
+            continue;
+        };
+        let my_shifted_translation = match *my_direction {
+            MovementDirection::Up => my_transform.translation + Vec3::new(0., **my_speed, 0.),
+            MovementDirection::Down => my_transform.translation + Vec3::new(0., -**my_speed, 0.),
+            MovementDirection::Left => my_transform.translation + Vec3::new(-**my_speed, 0., 0.),
+            MovementDirection::Right => my_transform.translation + Vec3::new(**my_speed, 0., 0.),
+            MovementDirection::None => my_transform.translation,
+        };
+
+        for (your_entity, your_transform, your_collider) in you.iter() {
             if my_entity == your_entity {
                 continue;
             }
 
-            let my_shifted_translation = match *my_direction {
-                MovementDirection::Up => my_transform.translation + Vec3::new(0., **my_speed, 0.),
-                MovementDirection::Down => {
-                    my_transform.translation + Vec3::new(0., -**my_speed, 0.)
-                }
-                MovementDirection::Left => {
-                    my_transform.translation + Vec3::new(-**my_speed, 0., 0.)
-                }
-                MovementDirection::Right => {
-                    my_transform.translation + Vec3::new(**my_speed, 0., 0.)
-                }
-                MovementDirection::None => my_transform.translation,
+            let Some(your_shape) = shape(
+                &images,
+                sprites.get(your_entity).ok(),
+                your_transform,
+                your_collider,
+            ) else {
+                continue;
             };
 
-            let collision = collide(
-                my_shifted_translation,
-                my_transform.scale.truncate(),
-                your_transform.translation,
-                your_transform.scale.truncate(),
-            );
-
-            if let Some(_) = collision {
+            if my_shape.intersects(
+                my_shifted_translation.truncate(),
+                your_shape,
+                your_transform.translation.truncate(),
+            ) {
                 collision_events.send_default();
 
                 *my_direction = MovementDirection::None;
@@ -58,3 +72,26 @@ pub fn check_for_collisions(
         }
     }
 }
+
+pub fn shape(
+    images: &Assets<Image>,
+    sprite: Option<(&Sprite, &Handle<Image>)>,
+    transform: &Transform,
+    collider: &Collider,
+) -> Option<Shape> {
+    // Without an explicit shape the sprite decides, and its size is unknown until the image loads
+    let shape = match collider.shape {
+        Some(shape) => shape,
+        None => {
+            let (sprite, texture) = sprite?;
+
+            Shape::Aabb(
+                sprite
+                    .custom_size
+                    .or_else(|| images.get(texture).map(Image::size))?,
+            )
+        }
+    };
+
+    Some(shape.scaled(transform.scale.truncate()))
+}
@@ -9,8 +9,109 @@ impl Default for Speed {
     }
 }
 
-#[derive(Component, Default, Debug)]
-pub struct Collider;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Shape {
+    Aabb(Vec2),
+    Circle(f32),
+}
+
+impl Shape {
+    pub fn scaled(self, scale: Vec2) -> Self {
+        match self {
+            Shape::Aabb(size) => Shape::Aabb(size * scale),
+            Shape::Circle(radius) => Shape::Circle(radius * scale.max_element()),
+        }
+    }
+
+    pub fn intersects(self, position: Vec2, other: Shape, other_position: Vec2) -> bool {
+        match (self, other) {
+            (Shape::Aabb(size), Shape::Aabb(other_size)) => {
+                let distance = (position - other_position).abs();
+                let reach = (size + other_size) / 2.;
+
+                distance.x < reach.x && distance.y < reach.y
+            }
+            (Shape::Circle(radius), Shape::Circle(other_radius)) => {
+                position.distance(other_position) < radius + other_radius
+            }
+            (Shape::Aabb(size), Shape::Circle(radius)) => {
+                let offset = other_position - position;
+                let closest = offset.clamp(-size / 2., size / 2.);
+
+                offset.distance(closest) < radius
+            }
+            (Shape::Circle(_), Shape::Aabb(_)) => other.intersects(other_position, self, position),
+        }
+    }
+}
+
+#[derive(Component, Default, Clone, Copy, Debug)]
+pub struct Collider {
+    pub shape: Option<Shape>,
+}
+
+impl Collider {
+    pub fn aabb(size: Vec2) -> Self {
+        Self {
+            shape: Some(Shape::Aabb(size)),
+        }
+    }
+
+    pub fn circle(radius: f32) -> Self {
+        Self {
+            shape: Some(Shape::Circle(radius)),
+        }
+    }
+}
 
 #[derive(Default, Debug)]
 pub struct CollisionEvent;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boxes_intersect_when_they_overlap() {
+        let tile = Shape::Aabb(Vec2::splat(16.));
+        let pillar = Shape::Aabb(Vec2::new(4., 40.));
+
+        assert!(tile.intersects(Vec2::ZERO, tile, Vec2::new(15., 15.)));
+        assert!(!tile.intersects(Vec2::ZERO, tile, Vec2::new(16., 0.)));
+        assert!(tile.intersects(Vec2::ZERO, pillar, Vec2::new(0., 27.)));
+        assert!(!tile.intersects(Vec2::ZERO, pillar, Vec2::new(10., 0.)));
+    }
+
+    #[test]
+    fn circles_intersect_within_both_radii() {
+        let circle = Shape::Circle(5.);
+
+        assert!(circle.intersects(Vec2::ZERO, Shape::Circle(3.), Vec2::new(7.9, 0.)));
+        assert!(!circle.intersects(Vec2::ZERO, Shape::Circle(3.), Vec2::new(6., 6.)));
+    }
+
+    #[test]
+    fn boxes_and_circles_use_the_closest_point() {
+        let tile = Shape::Aabb(Vec2::splat(16.));
+        let circle = Shape::Circle(4.);
+
+        assert!(tile.intersects(Vec2::ZERO, circle, Vec2::new(11., 0.)));
+        assert!(circle.intersects(Vec2::new(11., 0.), tile, Vec2::ZERO));
+        // Past a corner the distance is diagonal, not along either axis
+        assert!(!tile.intersects(Vec2::ZERO, circle, Vec2::new(11., 11.)));
+        assert!(!circle.intersects(Vec2::new(11., 11.), tile, Vec2::ZERO));
+        assert!(tile.intersects(Vec2::ZERO, circle, Vec2::ZERO));
+    }
+
+    #[test]
+    fn shapes_scale_with_the_transform() {
+        assert_eq!(
+            Shape::Aabb(Vec2::new(16., 8.)).scaled(Vec2::new(2., 0.5)),
+            Shape::Aabb(Vec2::new(32., 4.))
+        );
+        assert_eq!(
+            Shape::Circle(4.).scaled(Vec2::new(2., 3.)),
+            Shape::Circle(12.)
+        );
+    }
+}
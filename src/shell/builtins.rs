@@ -0,0 +1,1357 @@
+use super::{
+    command::{
+        complete_hostname, complete_path, Argument, Arguments, CommandRegistry, ShellCommand,
+    },
+    editor::EditMode,
+    model::AccessLevel,
+    session::{Hop, ShellOutput, ShellSession, StateChange},
+};
+use crate::host::{
+    database::DatabaseError,
+    monitoring::LogFile,
+    network::{Hosts, NetworkError, Protocol},
+    process::Process,
+    state::HostProperty,
+};
+
+const PROCESS_HEADER: &str = "   PID USER      %CPU  %MEM  COMMAND";
+const TAIL_LINES: usize = 10;
+
+pub fn register(registry: &mut CommandRegistry) {
+    registry
+        .register(Login)
+        .register(Logout)
+        .register(Clear)
+        .register(Show)
+        .register(Update)
+        .register(Save)
+        .register(List)
+        .register(ChangeDirectory)
+        .register(PrintDirectory)
+        .register(Concatenate)
+        .register(CopyFile)
+        .register(MoveFile)
+        .register(Remove)
+        .register(MakeDirectory)
+        .register(Migrate)
+        .register(Rollback)
+        .register(Db)
+        .register(Ssh)
+        .register(Exit)
+        .register(Ping)
+        .register(SecureCopy)
+        .register(ProcessStatus)
+        .register(Top)
+        .register(Kill)
+        .register(SystemControl)
+        .register(Tail)
+        .register(Grep)
+        .register(History)
+        .register(Set)
+        .register(Echo)
+        .register(Script)
+        .register(Audit);
+}
+
+struct Login;
+
+impl ShellCommand for Login {
+    fn name(&self) -> &'static str {
+        "login"
+    }
+
+    fn description(&self) -> &'static str {
+        "elevates permissions if access card is valid"
+    }
+
+    fn execute(&self, session: &mut ShellSession, host: &mut Hosts, _: &Arguments) -> ShellOutput {
+        let card = match &session.card {
+            Some(card) => card,
+            None => {
+                host.failed_logins += 1;
+                host.log(LogFile::Auth, "login failed: no access card");
+                return ShellOutput::error("login: no access card inserted");
+            }
+        };
+
+        if !card.grants(&session.hostname) || card.clearance <= AccessLevel::User {
+            host.failed_logins += 1;
+            host.log(
+                LogFile::Auth,
+                &format!("login failed: access card '{}' rejected", card.name),
+            );
+            return ShellOutput::error(format!("login: access card '{}' rejected", card.name));
+        }
+
+        if card.clearance <= session.access_level {
+            return ShellOutput::line("already logged in");
+        }
+
+        host.log(
+            LogFile::Auth,
+            &format!(
+                "login accepted: access card '{}' as {}",
+                card.name, card.clearance
+            ),
+        );
+
+        session.set_access_level(card.clearance, "logged in")
+    }
+}
+
+struct Logout;
+
+impl ShellCommand for Logout {
+    fn name(&self) -> &'static str {
+        "logout"
+    }
+
+    fn description(&self) -> &'static str {
+        "deelevates permissions"
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::Operator
+    }
+
+    fn execute(&self, session: &mut ShellSession, host: &mut Hosts, _: &Arguments) -> ShellOutput {
+        host.log(
+            LogFile::Auth,
+            &format!("session closed for {}", session.access_level),
+        );
+
+        session.set_access_level(AccessLevel::User, "logged out")
+    }
+}
+
+struct Clear;
+
+impl ShellCommand for Clear {
+    fn name(&self) -> &'static str {
+        "clear"
+    }
+
+    fn description(&self) -> &'static str {
+        "clears the screen"
+    }
+
+    fn execute(&self, _: &mut ShellSession, _: &mut Hosts, _: &Arguments) -> ShellOutput {
+        ShellOutput::default().change(StateChange::Cleared)
+    }
+}
+
+struct Show;
+
+impl ShellCommand for Show {
+    fn name(&self) -> &'static str {
+        "show"
+    }
+
+    fn description(&self) -> &'static str {
+        "prints a property of the host"
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Optional("property")]
+    }
+
+    fn complete(&self, _: &ShellSession, _: &Hosts, position: usize, _: &str) -> Vec<String> {
+        match position {
+            0 => HostProperty::ALL
+                .iter()
+                .map(|property| property.name().to_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn execute(
+        &self,
+        _: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let Some(property) = arguments.get(0) else {
+            return ShellOutput::lines(
+                HostProperty::ALL
+                    .iter()
+                    .map(|property| property.name().to_string())
+                    .collect(),
+            );
+        };
+
+        match HostProperty::try_from(property) {
+            Ok(property) => ShellOutput::lines(host.state.show(property)),
+            Err(error) => ShellOutput::error(error),
+        }
+    }
+}
+
+struct Update;
+
+impl ShellCommand for Update {
+    fn name(&self) -> &'static str {
+        "update"
+    }
+
+    fn description(&self) -> &'static str {
+        "deploys a release to the host"
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::Operator
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[
+            Argument::Flag("-f", AccessLevel::Root),
+            Argument::Optional("version"),
+        ]
+    }
+
+    fn complete(&self, _: &ShellSession, host: &Hosts, position: usize, _: &str) -> Vec<String> {
+        match position {
+            0 => host
+                .state
+                .releases
+                .iter()
+                .map(|release| release.version.clone())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn execute(
+        &self,
+        _: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let Some(version) = arguments.get(0) else {
+            let state = &host.state;
+
+            return ShellOutput::lines(
+                state
+                    .releases
+                    .iter()
+                    .map(|release| {
+                        let marker = if release.version == state.version {
+                            '*'
+                        } else {
+                            ' '
+                        };
+
+                        match &release.requires {
+                            Some(requires) => {
+                                format!("{marker} {} (requires {requires})", release.version)
+                            }
+                            None => format!("{marker} {}", release.version),
+                        }
+                    })
+                    .collect(),
+            );
+        };
+
+        match host.state.deploy(version, arguments.has_flag("-f")) {
+            Ok(deployment) => ShellOutput::line(deployment.to_string()),
+            Err(error) => ShellOutput::error(format!("update: {error}")),
+        }
+    }
+}
+
+struct Save;
+
+impl ShellCommand for Save {
+    fn name(&self) -> &'static str {
+        "save"
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::Operator
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[
+            Argument::Required("in-file"),
+            Argument::Required("out-file"),
+        ]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let (in_file, out_file) = (&arguments[0], &arguments[1]);
+
+        match host
+            .filesystem
+            .copy(&session.cwd, in_file, out_file, session.access_level)
+        {
+            Ok(()) => ShellOutput::line(format!("saved '{in_file}' to '{out_file}'")),
+            Err(error) => ShellOutput::error(format!("{in_file}: {error}")),
+        }
+    }
+}
+
+struct List;
+
+impl ShellCommand for List {
+    fn name(&self) -> &'static str {
+        "ls"
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Optional("path")]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let path = arguments.get(0).unwrap_or(".");
+
+        match host
+            .filesystem
+            .list(&session.cwd, path, session.access_level)
+        {
+            Ok(entries) => ShellOutput::lines(entries),
+            Err(error) => ShellOutput::error(error),
+        }
+    }
+}
+
+struct ChangeDirectory;
+
+impl ShellCommand for ChangeDirectory {
+    fn name(&self) -> &'static str {
+        "cd"
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Optional("path")]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let path = arguments.get(0).unwrap_or("/");
+
+        match host
+            .filesystem
+            .change_directory(&session.cwd, path, session.access_level)
+        {
+            Ok(cwd) => {
+                session.cwd = cwd;
+                ShellOutput::default()
+            }
+            Err(error) => ShellOutput::error(error),
+        }
+    }
+}
+
+struct PrintDirectory;
+
+impl ShellCommand for PrintDirectory {
+    fn name(&self) -> &'static str {
+        "pwd"
+    }
+
+    fn description(&self) -> &'static str {
+        "prints the working directory"
+    }
+
+    fn execute(&self, session: &mut ShellSession, _: &mut Hosts, _: &Arguments) -> ShellOutput {
+        ShellOutput::line(session.cwd.clone())
+    }
+}
+
+struct Concatenate;
+
+impl ShellCommand for Concatenate {
+    fn name(&self) -> &'static str {
+        "cat"
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Required("file")]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let path = &arguments[0];
+
+        match host
+            .filesystem
+            .read(&session.cwd, path, session.access_level)
+        {
+            Ok(contents) => ShellOutput::lines(contents.lines().map(String::from).collect()),
+            Err(error) => ShellOutput::error(format!("{path}: {error}")),
+        }
+    }
+}
+
+struct CopyFile;
+
+impl ShellCommand for CopyFile {
+    fn name(&self) -> &'static str {
+        "cp"
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::User
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Required("from"), Argument::Required("to")]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        ShellOutput::result(host.filesystem.copy(
+            &session.cwd,
+            &arguments[0],
+            &arguments[1],
+            session.access_level,
+        ))
+    }
+}
+
+struct MoveFile;
+
+impl ShellCommand for MoveFile {
+    fn name(&self) -> &'static str {
+        "mv"
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::User
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Required("from"), Argument::Required("to")]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        ShellOutput::result(host.filesystem.rename(
+            &session.cwd,
+            &arguments[0],
+            &arguments[1],
+            session.access_level,
+        ))
+    }
+}
+
+struct Remove;
+
+impl ShellCommand for Remove {
+    fn name(&self) -> &'static str {
+        "rm"
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::User
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Required("path")]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        ShellOutput::result(host.filesystem.remove(
+            &session.cwd,
+            &arguments[0],
+            session.access_level,
+        ))
+    }
+}
+
+struct MakeDirectory;
+
+impl ShellCommand for MakeDirectory {
+    fn name(&self) -> &'static str {
+        "mkdir"
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::User
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Required("path")]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        ShellOutput::result(host.filesystem.make_directory(
+            &session.cwd,
+            &arguments[0],
+            session.access_level,
+        ))
+    }
+}
+
+struct Migrate;
+
+impl ShellCommand for Migrate {
+    fn name(&self) -> &'static str {
+        "migrate"
+    }
+
+    fn description(&self) -> &'static str {
+        "runs pending database migrations"
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::Dba
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Optional("version")]
+    }
+
+    fn complete(&self, _: &ShellSession, host: &Hosts, position: usize, _: &str) -> Vec<String> {
+        match (position, &host.state.database) {
+            (0, Some(database)) => database
+                .migrations
+                .iter()
+                .filter(|migration| migration.version > database.schema_version)
+                .map(|migration| migration.version.to_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn execute(
+        &self,
+        _: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let target = match arguments.get(0).map(str::parse::<u32>) {
+            None => None,
+            Some(Ok(version)) => Some(version),
+            Some(Err(_)) => {
+                return ShellOutput::error(format!("migrate: invalid version '{}'", &arguments[0]))
+            }
+        };
+
+        let result = host
+            .state
+            .database
+            .as_mut()
+            .ok_or(DatabaseError::NoDatabase)
+            .and_then(|database| database.migrate(target));
+
+        match result {
+            Ok(running) => ShellOutput::line(format!("started {running}")),
+            Err(error) => ShellOutput::error(format!("migrate: {error}")),
+        }
+    }
+}
+
+struct Rollback;
+
+impl ShellCommand for Rollback {
+    fn name(&self) -> &'static str {
+        "rollback"
+    }
+
+    fn description(&self) -> &'static str {
+        "reverts the latest database migration"
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::Dba
+    }
+
+    fn execute(&self, _: &mut ShellSession, host: &mut Hosts, _: &Arguments) -> ShellOutput {
+        let result = host
+            .state
+            .database
+            .as_mut()
+            .ok_or(DatabaseError::NoDatabase)
+            .and_then(|database| database.rollback());
+
+        match result {
+            Ok(running) => ShellOutput::line(format!("started {running}")),
+            Err(error) => ShellOutput::error(format!("rollback: {error}")),
+        }
+    }
+}
+
+struct Db;
+
+impl ShellCommand for Db {
+    fn name(&self) -> &'static str {
+        "db"
+    }
+
+    fn description(&self) -> &'static str {
+        "inspects the database, unlock releases a stale lock"
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::Operator
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Required("status|unlock")]
+    }
+
+    fn complete(&self, _: &ShellSession, _: &Hosts, position: usize, _: &str) -> Vec<String> {
+        match position {
+            0 => vec![String::from("status"), String::from("unlock")],
+            _ => Vec::new(),
+        }
+    }
+
+    fn permitted(&self, access_level: AccessLevel, arguments: &Arguments) -> bool {
+        match arguments.get(0) {
+            Some("unlock") => access_level >= AccessLevel::Dba,
+            _ => access_level >= self.access_level(),
+        }
+    }
+
+    fn execute(
+        &self,
+        _: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let Some(database) = &mut host.state.database else {
+            return ShellOutput::error(format!("db: {}", DatabaseError::NoDatabase));
+        };
+
+        match &arguments[0] {
+            "status" => ShellOutput::lines(database.status()),
+            "unlock" => match database.unlock() {
+                Ok(()) => ShellOutput::line("lock released"),
+                Err(error) => ShellOutput::error(format!("db: {error}")),
+            },
+            action => ShellOutput::error(format!("db: unknown action '{action}'")),
+        }
+    }
+}
+
+struct Ssh;
+
+impl ShellCommand for Ssh {
+    fn name(&self) -> &'static str {
+        "ssh"
+    }
+
+    fn description(&self) -> &'static str {
+        "connects to another host"
+    }
+
+    fn complete(&self, _: &ShellSession, host: &Hosts, position: usize, _: &str) -> Vec<String> {
+        match position {
+            0 => complete_hostname(host),
+            _ => Vec::new(),
+        }
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::User
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Required("host")]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        match host
+            .network
+            .connect(&session.hostname, &arguments[0], Protocol::Ssh)
+        {
+            Ok((hostname, target)) => {
+                let output =
+                    ShellOutput::line(format!("connected to {hostname} ({})", target.address));
+                let message = format!("accepted ssh connection from {}", session.hostname);
+
+                if let Some(target) = host.get_mut(hostname) {
+                    target.log(LogFile::Auth, &message);
+                }
+
+                session.connect(hostname);
+                output
+            }
+            Err(error) => ShellOutput::error(format!("ssh: {error}")),
+        }
+    }
+}
+
+struct Exit;
+
+impl ShellCommand for Exit {
+    fn name(&self) -> &'static str {
+        "exit"
+    }
+
+    fn description(&self) -> &'static str {
+        "closes the connection to the current host"
+    }
+
+    fn execute(&self, session: &mut ShellSession, _: &mut Hosts, _: &Arguments) -> ShellOutput {
+        match session.disconnect() {
+            Some(hostname) => ShellOutput::line(format!("connection to {hostname} closed"))
+                .change(StateChange::AccessLevel(session.access_level)),
+            None => ShellOutput::error("exit: not connected to a remote host"),
+        }
+    }
+}
+
+struct Ping;
+
+impl ShellCommand for Ping {
+    fn name(&self) -> &'static str {
+        "ping"
+    }
+
+    fn description(&self) -> &'static str {
+        "checks whether a host is reachable"
+    }
+
+    fn complete(&self, _: &ShellSession, host: &Hosts, position: usize, _: &str) -> Vec<String> {
+        match position {
+            0 => complete_hostname(host),
+            _ => Vec::new(),
+        }
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Required("host")]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let network = host.network;
+
+        match network.connect(&session.hostname, &arguments[0], Protocol::Icmp) {
+            Ok((hostname, target)) => ShellOutput::line(format!(
+                "{hostname} ({}): 3 packets transmitted, 3 received",
+                target.address
+            )),
+            Err(error @ NetworkError::UnknownHost(_)) => {
+                ShellOutput::error(format!("ping: {error}"))
+            }
+            Err(_) => match network.resolve(&arguments[0]) {
+                Ok((hostname, target)) => ShellOutput::line(format!(
+                    "{hostname} ({}): 3 packets transmitted, 0 received",
+                    target.address
+                )),
+                Err(error) => ShellOutput::error(format!("ping: {error}")),
+            },
+        }
+    }
+}
+
+struct SecureCopy;
+
+impl SecureCopy {
+    fn endpoint<'a>(
+        session: &ShellSession,
+        host: &Hosts,
+        argument: &'a str,
+    ) -> Result<(Hop, &'a str), NetworkError> {
+        let Some((hostname, path)) = argument.split_once(':') else {
+            let local = Hop {
+                hostname: session.hostname.clone(),
+                access_level: session.access_level,
+                cwd: session.cwd.clone(),
+            };
+
+            return Ok((local, argument));
+        };
+
+        let (hostname, _) = host
+            .network
+            .connect(&session.hostname, hostname, Protocol::Ssh)?;
+
+        Ok((Hop::remote(hostname), path))
+    }
+}
+
+impl ShellCommand for SecureCopy {
+    fn name(&self) -> &'static str {
+        "scp"
+    }
+
+    fn description(&self) -> &'static str {
+        "copies files between hosts, remote paths are written as host:path"
+    }
+
+    fn complete(
+        &self,
+        session: &ShellSession,
+        host: &Hosts,
+        _: usize,
+        partial: &str,
+    ) -> Vec<String> {
+        let mut candidates = complete_path(session, host, partial);
+
+        candidates.extend(
+            complete_hostname(host)
+                .into_iter()
+                .map(|hostname| format!("{hostname}:")),
+        );
+        candidates
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::User
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Required("from"), Argument::Required("to")]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let endpoints = Self::endpoint(session, host, &arguments[0])
+            .and_then(|from| Ok((from, Self::endpoint(session, host, &arguments[1])?)));
+
+        let ((source, from), (destination, to)) = match endpoints {
+            Ok(endpoints) => endpoints,
+            Err(error) => return ShellOutput::error(format!("scp: {error}")),
+        };
+
+        let contents = match host.get_mut(&source.hostname).map(|host| {
+            host.filesystem
+                .read(&source.cwd, from, source.access_level)
+                .map(String::from)
+        }) {
+            Some(Ok(contents)) => contents,
+            Some(Err(error)) => return ShellOutput::error(format!("scp: {from}: {error}")),
+            None => {
+                return ShellOutput::error(format!("scp: {} is not responding", source.hostname))
+            }
+        };
+
+        let Some(target) = host.get_mut(&destination.hostname) else {
+            return ShellOutput::error(format!("scp: {} is not responding", destination.hostname));
+        };
+
+        let to = target
+            .filesystem
+            .target(&destination.cwd, from, to, destination.access_level);
+
+        match target
+            .filesystem
+            .write(&destination.cwd, &to, &contents, destination.access_level)
+        {
+            Ok(()) => ShellOutput::default(),
+            Err(error) => ShellOutput::error(format!("scp: {to}: {error}")),
+        }
+    }
+}
+
+fn process_line(process: &Process) -> String {
+    format!(
+        "{:>6} {:<9}{:>5.1}{:>6.1}  {}",
+        process.pid,
+        process.owner.to_string(),
+        process.cpu,
+        process.memory,
+        process.command
+    )
+}
+
+struct ProcessStatus;
+
+impl ShellCommand for ProcessStatus {
+    fn name(&self) -> &'static str {
+        "ps"
+    }
+
+    fn description(&self) -> &'static str {
+        "lists running processes"
+    }
+
+    fn execute(&self, _: &mut ShellSession, host: &mut Hosts, _: &Arguments) -> ShellOutput {
+        let mut lines = vec![String::from(PROCESS_HEADER)];
+
+        lines.extend(host.state.processes.processes().map(process_line));
+
+        ShellOutput::lines(lines)
+    }
+}
+
+struct Top;
+
+impl ShellCommand for Top {
+    fn name(&self) -> &'static str {
+        "top"
+    }
+
+    fn description(&self) -> &'static str {
+        "shows load and the busiest processes"
+    }
+
+    fn execute(&self, _: &mut ShellSession, host: &mut Hosts, _: &Arguments) -> ShellOutput {
+        let state = &host.state;
+        let mut processes = state.processes.processes().collect::<Vec<_>>();
+
+        processes.sort_by(|a, b| b.cpu.total_cmp(&a.cpu));
+
+        let mut lines = vec![
+            format!(
+                "load average: {:.2}, {} processes, {} services active",
+                state.load,
+                processes.len(),
+                state.processes.active_count()
+            ),
+            String::from(PROCESS_HEADER),
+        ];
+
+        lines.extend(processes.into_iter().take(10).map(process_line));
+
+        ShellOutput::lines(lines)
+    }
+}
+
+struct Kill;
+
+impl ShellCommand for Kill {
+    fn name(&self) -> &'static str {
+        "kill"
+    }
+
+    fn description(&self) -> &'static str {
+        "terminates a process"
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::User
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[
+            Argument::Required("pid"),
+            Argument::Flag("-9", AccessLevel::User),
+        ]
+    }
+
+    fn complete(&self, _: &ShellSession, host: &Hosts, position: usize, _: &str) -> Vec<String> {
+        match position {
+            0 => host
+                .state
+                .processes
+                .processes()
+                .map(|process| process.pid.to_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let Ok(pid) = arguments[0].parse::<u32>() else {
+            return ShellOutput::error(format!("kill: invalid pid '{}'", &arguments[0]));
+        };
+
+        match host.state.processes.kill(pid, session.access_level) {
+            Ok(Some(service)) => ShellOutput::line(format!("{service} terminated")),
+            Ok(None) => ShellOutput::default(),
+            Err(error) => ShellOutput::error(format!("kill: {error}")),
+        }
+    }
+}
+
+struct SystemControl;
+
+impl ShellCommand for SystemControl {
+    fn name(&self) -> &'static str {
+        "systemctl"
+    }
+
+    fn description(&self) -> &'static str {
+        "starts, stops, restarts or inspects a service"
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::User
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[
+            Argument::Required("start|stop|restart|status"),
+            Argument::Required("service"),
+        ]
+    }
+
+    fn complete(&self, _: &ShellSession, host: &Hosts, position: usize, _: &str) -> Vec<String> {
+        match position {
+            0 => ["start", "stop", "restart", "status"]
+                .map(String::from)
+                .to_vec(),
+            1 => host
+                .state
+                .processes
+                .services()
+                .map(|service| service.name.clone())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn permitted(&self, access_level: AccessLevel, arguments: &Arguments) -> bool {
+        match arguments.get(0) {
+            Some("status") => access_level >= self.access_level(),
+            _ => access_level >= AccessLevel::Operator,
+        }
+    }
+
+    fn execute(
+        &self,
+        _: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let (action, service) = (&arguments[0], &arguments[1]);
+        let crash_loop = host.state.crash_looping();
+        let processes = &mut host.state.processes;
+
+        let result = match action {
+            "start" => processes.start(service, crash_loop),
+            "stop" => processes.stop(service),
+            "restart" => processes.restart(service, crash_loop),
+            "status" => {
+                return match processes.services().find(|unit| unit.name == *service) {
+                    Some(unit) => ShellOutput::line(format!("{service}: {}", unit.state)),
+                    None => ShellOutput::error(format!("systemctl: unit {service} not found")),
+                }
+            }
+            action => return ShellOutput::error(format!("systemctl: unknown action '{action}'")),
+        };
+
+        match result {
+            Ok(()) => ShellOutput::default(),
+            Err(error) => ShellOutput::error(format!("systemctl: {error}")),
+        }
+    }
+}
+
+struct Tail;
+
+impl ShellCommand for Tail {
+    fn name(&self) -> &'static str {
+        "tail"
+    }
+
+    fn description(&self) -> &'static str {
+        "prints the last lines of a file"
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Optional("file")]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let lines = match (arguments.get(0), arguments.stdin()) {
+            (Some(path), _) => match host
+                .filesystem
+                .read(&session.cwd, path, session.access_level)
+            {
+                Ok(contents) => contents.lines().map(str::to_string).collect(),
+                Err(error) => return ShellOutput::error(format!("{path}: {error}")),
+            },
+            (None, Some(stdin)) => stdin.to_vec(),
+            (None, None) => return ShellOutput::error("tail: no input file"),
+        };
+
+        ShellOutput::lines(lines[lines.len().saturating_sub(TAIL_LINES)..].to_vec())
+    }
+}
+
+struct Grep;
+
+impl ShellCommand for Grep {
+    fn name(&self) -> &'static str {
+        "grep"
+    }
+
+    fn description(&self) -> &'static str {
+        "prints lines of files containing a pattern"
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Required("pattern"), Argument::Variadic("file")]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let (pattern, paths) = (&arguments[0], &arguments.positional()[1..]);
+
+        if paths.is_empty() {
+            return match arguments.stdin() {
+                Some(stdin) => ShellOutput::lines(
+                    stdin
+                        .iter()
+                        .filter(|line| line.contains(pattern))
+                        .cloned()
+                        .collect(),
+                ),
+                None => ShellOutput::error("grep: no input files"),
+            };
+        }
+
+        let mut lines = Vec::new();
+
+        for path in paths {
+            let contents = match host
+                .filesystem
+                .read(&session.cwd, path, session.access_level)
+            {
+                Ok(contents) => contents,
+                Err(error) => return ShellOutput::error(format!("grep: {path}: {error}")),
+            };
+
+            lines.extend(
+                contents
+                    .lines()
+                    .filter(|line| line.contains(pattern))
+                    .map(|line| match paths.len() {
+                        1 => line.to_string(),
+                        _ => format!("{path}:{line}"),
+                    }),
+            );
+        }
+
+        ShellOutput::lines(lines)
+    }
+}
+
+struct History;
+
+impl ShellCommand for History {
+    fn name(&self) -> &'static str {
+        "history"
+    }
+
+    fn description(&self) -> &'static str {
+        "lists previous commands"
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Flag("-c", AccessLevel::Operator)]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        if arguments.has_flag("-c") {
+            session.history.clear();
+            host.clear_history();
+            return ShellOutput::default();
+        }
+
+        ShellOutput::lines(
+            session
+                .history
+                .entries()
+                .enumerate()
+                .map(|(idx, line)| format!("{:>4}  {line}", idx + 1))
+                .collect(),
+        )
+    }
+}
+
+struct Echo;
+
+impl ShellCommand for Echo {
+    fn name(&self) -> &'static str {
+        "echo"
+    }
+
+    fn description(&self) -> &'static str {
+        "prints its arguments"
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Variadic("text")]
+    }
+
+    fn execute(&self, _: &mut ShellSession, _: &mut Hosts, arguments: &Arguments) -> ShellOutput {
+        ShellOutput::line(arguments.positional().join(" "))
+    }
+}
+
+struct Script;
+
+impl ShellCommand for Script {
+    fn name(&self) -> &'static str {
+        "sh"
+    }
+
+    fn description(&self) -> &'static str {
+        "runs the commands in a script file"
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Required("script")]
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let path = &arguments[0];
+
+        match host
+            .filesystem
+            .read(&session.cwd, path, session.access_level)
+        {
+            Ok(script) => ShellOutput::default().change(StateChange::Script(script.to_string())),
+            Err(error) => ShellOutput::error(format!("{path}: {error}")),
+        }
+    }
+}
+
+struct Set;
+
+impl ShellCommand for Set {
+    fn name(&self) -> &'static str {
+        "set"
+    }
+
+    fn description(&self) -> &'static str {
+        "switches between emacs and vi line editing"
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[
+            Argument::Flag("-o", AccessLevel::Guest),
+            Argument::Optional("mode"),
+        ]
+    }
+
+    fn complete(&self, _: &ShellSession, _: &Hosts, position: usize, _: &str) -> Vec<String> {
+        match position {
+            0 => vec![String::from("emacs"), String::from("vi")],
+            _ => Vec::new(),
+        }
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        _: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        let editor = &mut session.editor;
+
+        match arguments.get(0) {
+            None => ShellOutput::lines(vec![
+                format!("emacs  {}", if editor.vi { "off" } else { "on" }),
+                format!("vi     {}", if editor.vi { "on" } else { "off" }),
+            ]),
+            Some("emacs") => {
+                editor.vi = false;
+                editor.mode = EditMode::Insert;
+                ShellOutput::default()
+            }
+            Some("vi") => {
+                editor.vi = true;
+                ShellOutput::default()
+            }
+            Some(mode) => ShellOutput::error(format!("unknown editing mode '{mode}'")),
+        }
+    }
+}
+
+struct Audit;
+
+impl ShellCommand for Audit {
+    fn name(&self) -> &'static str {
+        "audit"
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::Root
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[Argument::Flag("-c", AccessLevel::Root)]
+    }
+
+    fn execute(
+        &self,
+        _: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput {
+        if arguments.has_flag("-c") {
+            host.audit.clear();
+            host.failed_logins = 0;
+            return ShellOutput::line("audit log purged");
+        }
+
+        ShellOutput::lines(
+            host.audit
+                .iter()
+                .map(|entry| format!("{}: {}", entry.access_level, entry.command))
+                .collect(),
+        )
+    }
+}
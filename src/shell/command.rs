@@ -0,0 +1,371 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+
+use super::{
+    builtins,
+    model::AccessLevel,
+    session::{ShellOutput, ShellSession},
+};
+use crate::host::network::Hosts;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Argument {
+    Required(&'static str),
+    Optional(&'static str),
+    Variadic(&'static str),
+    Flag(&'static str, AccessLevel),
+}
+
+impl std::fmt::Display for Argument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Argument::Required(name) => write!(f, "<{name}>"),
+            Argument::Optional(name) => write!(f, "[{name}]"),
+            Argument::Variadic(name) => write!(f, "[{name}...]"),
+            Argument::Flag(flag, _) => write!(f, "[{flag}]"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Arguments {
+    positional: Vec<String>,
+    flags: Vec<String>,
+    stdin: Option<Vec<String>>,
+}
+
+impl Arguments {
+    pub fn parse(command: &dyn ShellCommand, words: Vec<String>) -> Result<Self, String> {
+        let mut arguments = Self::default();
+        let mut words = words.into_iter();
+
+        for word in words.by_ref() {
+            if word == "--" {
+                break;
+            } else if is_flag(command, &word) {
+                arguments.flags.push(word);
+            } else {
+                arguments.positional.push(word);
+            }
+        }
+
+        arguments.positional.extend(words);
+
+        if let Some(Argument::Required(name)) = command
+            .arguments()
+            .iter()
+            .filter(|argument| !matches!(argument, Argument::Flag(..)))
+            .nth(arguments.positional.len())
+        {
+            return Err(format!("{}: missing argument <{name}>", command.name()));
+        }
+
+        let mut maximum = Some(0);
+
+        for argument in command.arguments() {
+            maximum = match argument {
+                Argument::Required(_) | Argument::Optional(_) => maximum.map(|maximum| maximum + 1),
+                Argument::Variadic(_) => None,
+                Argument::Flag(..) => maximum,
+            };
+        }
+
+        if maximum.is_some_and(|maximum| arguments.positional.len() > maximum) {
+            return Err(format!("{}: too many arguments", command.name()));
+        }
+
+        Ok(arguments)
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&str> {
+        self.positional.get(idx).map(String::as_str)
+    }
+
+    pub fn positional(&self) -> &[String] {
+        &self.positional
+    }
+
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|argument| argument == flag)
+    }
+
+    pub fn stdin(&self) -> Option<&[String]> {
+        self.stdin.as_deref()
+    }
+
+    pub fn with_stdin(mut self, stdin: Option<Vec<String>>) -> Self {
+        self.stdin = stdin;
+        self
+    }
+}
+
+impl std::ops::Index<usize> for Arguments {
+    type Output = str;
+
+    fn index(&self, idx: usize) -> &Self::Output {
+        &self.positional[idx]
+    }
+}
+
+pub trait ShellCommand: Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+
+    fn description(&self) -> &'static str {
+        ""
+    }
+
+    fn access_level(&self) -> AccessLevel {
+        AccessLevel::Guest
+    }
+
+    fn arguments(&self) -> &'static [Argument] {
+        &[]
+    }
+
+    fn complete(
+        &self,
+        session: &ShellSession,
+        host: &Hosts,
+        position: usize,
+        partial: &str,
+    ) -> Vec<String> {
+        let mut positional = self
+            .arguments()
+            .iter()
+            .filter(|argument| !matches!(argument, Argument::Flag(..)));
+
+        let declared = positional
+            .clone()
+            .nth(position)
+            .or(positional.find(|argument| matches!(argument, Argument::Variadic(_))));
+
+        match declared {
+            Some(_) => complete_path(session, host, partial),
+            None => Vec::new(),
+        }
+    }
+
+    fn execute(
+        &self,
+        session: &mut ShellSession,
+        host: &mut Hosts,
+        arguments: &Arguments,
+    ) -> ShellOutput;
+
+    fn synopsis(&self) -> String {
+        let mut synopsis = vec![self.name().to_string()];
+
+        synopsis.extend(self.arguments().iter().map(|argument| argument.to_string()));
+
+        synopsis.join(" ")
+    }
+
+    fn usage(&self) -> String {
+        match self.description() {
+            "" => self.synopsis(),
+            description => format!("{} ({description})", self.synopsis()),
+        }
+    }
+
+    fn permitted(&self, access_level: AccessLevel, arguments: &Arguments) -> bool {
+        access_level >= self.access_level()
+            && self.arguments().iter().all(|argument| match argument {
+                Argument::Flag(flag, minimum) => {
+                    access_level >= *minimum || !arguments.has_flag(flag)
+                }
+                _ => true,
+            })
+    }
+}
+
+pub fn is_flag(command: &dyn ShellCommand, word: &str) -> bool {
+    command
+        .arguments()
+        .iter()
+        .any(|argument| matches!(argument, Argument::Flag(flag, _) if *flag == word))
+}
+
+pub fn complete_path(session: &ShellSession, host: &Hosts, partial: &str) -> Vec<String> {
+    let (directory, prefix) = match partial.rsplit_once('/') {
+        Some(("", _)) => ("/", "/".to_string()),
+        Some((directory, _)) => (directory, format!("{directory}/")),
+        None => (".", String::new()),
+    };
+
+    host.filesystem
+        .list(&session.cwd, directory, session.access_level)
+        .map(|entries| {
+            entries
+                .into_iter()
+                .map(|entry| format!("{prefix}{entry}"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn complete_hostname(host: &Hosts) -> Vec<String> {
+    host.network.hostnames().cloned().collect()
+}
+
+#[derive(Resource)]
+pub struct CommandRegistry {
+    commands: BTreeMap<&'static str, Box<dyn ShellCommand>>,
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            commands: BTreeMap::new(),
+        };
+
+        builtins::register(&mut registry);
+
+        registry
+    }
+}
+
+impl CommandRegistry {
+    pub fn register(&mut self, command: impl ShellCommand) -> &mut Self {
+        self.commands.insert(command.name(), Box::new(command));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn ShellCommand> {
+        self.commands.get(name).map(Box::as_ref)
+    }
+
+    pub fn available(&self, access_level: AccessLevel) -> impl Iterator<Item = &dyn ShellCommand> {
+        self.commands
+            .values()
+            .map(Box::as_ref)
+            .filter(move |command| access_level >= command.access_level())
+    }
+
+    pub fn describe(&self, access_level: AccessLevel, prefix: &str) -> Vec<String> {
+        self.available(access_level)
+            .filter(|command| command.name().starts_with(prefix))
+            .map(|command| command.usage())
+            .collect()
+    }
+}
+
+pub trait RegisterShellCommand {
+    fn register_shell_command(&mut self, command: impl ShellCommand) -> &mut Self;
+}
+
+impl RegisterShellCommand for App {
+    fn register_shell_command(&mut self, command: impl ShellCommand) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(CommandRegistry::default)
+            .register(command);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct CopyFiles;
+
+    impl ShellCommand for CopyFiles {
+        fn name(&self) -> &'static str {
+            "copy"
+        }
+
+        fn description(&self) -> &'static str {
+            "copy files"
+        }
+
+        fn arguments(&self) -> &'static [Argument] {
+            &[
+                Argument::Flag("-f", AccessLevel::Root),
+                Argument::Required("from"),
+                Argument::Optional("to"),
+            ]
+        }
+
+        fn execute(
+            &self,
+            _session: &mut ShellSession,
+            _host: &mut Hosts,
+            _arguments: &Arguments,
+        ) -> ShellOutput {
+            ShellOutput::default()
+        }
+    }
+
+    fn parse(words: &[&str]) -> Result<Arguments, String> {
+        Arguments::parse(
+            &CopyFiles,
+            words.iter().map(|word| word.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn declared_flags_are_separated() {
+        let arguments = parse(&["a", "-f", "b"]).unwrap();
+
+        assert!(arguments.has_flag("-f"));
+        assert_eq!(arguments.positional(), ["a", "b"]);
+    }
+
+    #[test]
+    fn other_dash_words_are_positional() {
+        let arguments = parse(&["-5", "-x"]).unwrap();
+
+        assert!(!arguments.has_flag("-x"));
+        assert_eq!(&arguments[0], "-5");
+        assert_eq!(arguments.get(1), Some("-x"));
+    }
+
+    #[test]
+    fn double_dash_ends_flags() {
+        let arguments = parse(&["--", "-f"]).unwrap();
+
+        assert!(!arguments.has_flag("-f"));
+        assert_eq!(arguments.positional(), ["-f"]);
+    }
+
+    #[test]
+    fn argument_counts_are_checked() {
+        assert_eq!(
+            parse(&["-f"]),
+            Err(String::from("copy: missing argument <from>"))
+        );
+        assert_eq!(
+            parse(&["a", "b", "c"]),
+            Err(String::from("copy: too many arguments"))
+        );
+    }
+
+    #[test]
+    fn flags_are_permitted_by_access_level() {
+        let arguments = parse(&["-f", "a"]).unwrap();
+
+        assert!(!CopyFiles.permitted(AccessLevel::User, &arguments));
+        assert!(CopyFiles.permitted(AccessLevel::Root, &arguments));
+        assert!(CopyFiles.permitted(AccessLevel::User, &parse(&["a"]).unwrap()));
+    }
+
+    #[test]
+    fn usage_lists_the_arguments() {
+        assert_eq!(CopyFiles.usage(), "copy [-f] <from> [to] (copy files)");
+    }
+
+    #[test]
+    fn registry_finds_registered_commands() {
+        let mut registry = CommandRegistry::default();
+
+        registry.register(CopyFiles);
+
+        assert!(registry
+            .available(AccessLevel::Guest)
+            .any(|command| command.name() == "copy"));
+        assert_eq!(
+            registry.get("copy").map(|command| command.usage()),
+            Some(String::from("copy [-f] <from> [to] (copy files)"))
+        );
+    }
+}
@@ -1,37 +1,278 @@
 use bevy::prelude::*;
 
-use super::model::{AccessLevel, CurrentPosition, Hostname, Selected, ShellCommands, ShellData};
+use super::{
+    command::CommandRegistry,
+    editor::{EditAction, EditMode, LineEditor},
+    model::{EvidenceEvent, Hostname, Selected, ShellData},
+    session::{ShellOutput, ShellSession, StateChange},
+    SHELL_HEIGHT,
+};
+use crate::host::{
+    forensics::Evidence,
+    model::Host,
+    network::{Hosts, Network},
+};
 
 pub fn input(
-    keys: Res<Input<KeyCode>>,
+    mut keys: ResMut<Input<KeyCode>>,
+    registry: Res<CommandRegistry>,
+    network: Res<Network>,
     mut event_reader: EventReader<ReceivedCharacter>,
-    mut shells: Query<
-        (
-            &mut CurrentPosition,
-            &mut ShellData,
-            &mut AccessLevel,
-            &Hostname,
-        ),
-        With<Selected>,
-    >,
+    mut shells: Query<(&mut ShellData, &mut ShellSession), With<Selected>>,
+    mut hosts: Query<(&Hostname, &mut Host)>,
+    mut completing: Local<bool>,
 ) {
-    for (mut current_position, mut shell_data, mut access_level, hostname) in shells.iter_mut() {
+    if keys.get_just_pressed().any(|key| *key != KeyCode::Tab) {
+        *completing = false;
+    }
+
+    let shift = keys.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+    let control = keys.any_pressed([KeyCode::LControl, KeyCode::RControl]);
+
+    for (mut shell_data, mut session) in shells.iter_mut() {
+        if session.history.is_searching() {
+            reverse_search(&keys, &mut event_reader, &mut shell_data, &mut session);
+
+            if keys.just_pressed(KeyCode::Return) {
+                execute(
+                    &registry,
+                    &mut shell_data,
+                    &mut session,
+                    &mut connect(&network, &mut hosts),
+                );
+            }
+
+            continue;
+        }
+
         if keys.just_pressed(KeyCode::Return) {
-            ShellCommands::process(
-                &mut access_level,
+            execute(
+                &registry,
                 &mut shell_data,
-                &mut current_position,
-                hostname.clone(),
+                &mut session,
+                &mut connect(&network, &mut hosts),
             );
-        } else if keys.just_pressed(KeyCode::Back) {
-            shell_data.pop(&mut current_position);
+        } else if keys.just_pressed(KeyCode::Tab) {
+            let line = session.editor.line();
+
+            if let Some(completion) =
+                session.complete(&registry, &mut connect(&network, &mut hosts), &line)
+            {
+                if completion.line != line {
+                    session.editor.set(&completion.line);
+                    *completing = false;
+                } else if *completing {
+                    shell_data.print(&ShellOutput::line(completion.candidates.join("  ")));
+                } else {
+                    *completing = true;
+                }
+            }
+        } else if keys.just_pressed(KeyCode::Up) {
+            history_older(&mut session);
+        } else if keys.just_pressed(KeyCode::Down) {
+            history_newer(&mut session);
+        } else if control && keys.just_pressed(KeyCode::R) {
+            let line = session.editor.line();
+            session.history.start_search(&line);
+            shell_data.replace_line(&session.history.search_line().unwrap_or_default());
+            continue;
+        } else if shift && keys.just_pressed(KeyCode::PageUp) {
+            shell_data.scroll_up(SHELL_HEIGHT - 1);
+            continue;
+        } else if shift && keys.just_pressed(KeyCode::PageDown) {
+            shell_data.scroll_down(SHELL_HEIGHT - 1);
+            continue;
+        } else if keys.just_pressed(KeyCode::Escape) {
+            session.editor.escape();
+        } else if control {
+            edit_control(&keys, &mut session.editor);
         } else {
-            ShellData::insert(
-                &mut event_reader,
-                shell_data,
-                current_position,
-                access_level,
-            );
+            edit(&keys, &mut session.editor);
+
+            if insert(&mut event_reader, &mut session) {
+                let line = session.editor.line();
+                let help = session.help(&registry, &mut connect(&network, &mut hosts), &line);
+
+                shell_data.print(&help);
+            }
+        }
+
+        shell_data.redraw(&format!("{}{}", session.prompt(), session.editor.line()));
+    }
+
+    // Escape belongs to the line editor while a terminal is open, so it must not pause the game
+    keys.clear_just_pressed(KeyCode::Escape);
+}
+
+fn edit(keys: &Input<KeyCode>, editor: &mut LineEditor) {
+    if keys.just_pressed(KeyCode::Back) {
+        match editor.mode {
+            EditMode::Insert => editor.backspace(),
+            EditMode::Normal => editor.left(),
+        }
+    } else if keys.just_pressed(KeyCode::Delete) {
+        editor.delete();
+    } else if keys.just_pressed(KeyCode::Left) {
+        editor.left();
+    } else if keys.just_pressed(KeyCode::Right) {
+        editor.right();
+    } else if keys.just_pressed(KeyCode::Home) {
+        editor.home();
+    } else if keys.just_pressed(KeyCode::End) {
+        editor.end();
+    }
+}
+
+fn edit_control(keys: &Input<KeyCode>, editor: &mut LineEditor) {
+    if keys.just_pressed(KeyCode::A) {
+        editor.home();
+    } else if keys.just_pressed(KeyCode::E) {
+        editor.end();
+    } else if keys.just_pressed(KeyCode::U) {
+        editor.kill_before();
+    } else if keys.just_pressed(KeyCode::K) {
+        editor.kill_after();
+    } else if keys.just_pressed(KeyCode::W) {
+        editor.kill_word();
+    } else if keys.just_pressed(KeyCode::Left) {
+        editor.word_backward();
+    } else if keys.just_pressed(KeyCode::Right) {
+        editor.word_forward();
+    }
+}
+
+fn insert(event_reader: &mut EventReader<ReceivedCharacter>, session: &mut ShellSession) -> bool {
+    let mut help = false;
+
+    for event in event_reader.iter() {
+        if event.char.is_control() {
+            continue;
+        }
+
+        if session.editor.mode == EditMode::Normal {
+            match session.editor.normal(event.char) {
+                Some(EditAction::HistoryOlder) => history_older(session),
+                Some(EditAction::HistoryNewer) => history_newer(session),
+                None => (),
+            }
+        } else if event.char == '?' && !matches!(session.editor.previous(), Some('$' | '\\')) {
+            help = true;
+        } else {
+            session.editor.insert(event.char);
+        }
+    }
+
+    help
+}
+
+fn history_older(session: &mut ShellSession) {
+    let draft = session.editor.line();
+
+    if let Some(line) = session.history.older(&draft) {
+        session.editor.set(line);
+    }
+}
+
+fn history_newer(session: &mut ShellSession) {
+    if let Some(line) = session.history.newer() {
+        session.editor.set(line);
+    }
+}
+
+fn connect<'a>(network: &'a Network, hosts: &'a mut Query<(&Hostname, &mut Host)>) -> Hosts<'a> {
+    Hosts::new(
+        network,
+        hosts
+            .iter_mut()
+            .map(|(hostname, host)| (hostname.to_string(), host.into_inner())),
+    )
+}
+
+fn reverse_search(
+    keys: &Input<KeyCode>,
+    event_reader: &mut EventReader<ReceivedCharacter>,
+    shell_data: &mut ShellData,
+    session: &mut ShellSession,
+) {
+    let control = keys.any_pressed([KeyCode::LControl, KeyCode::RControl]);
+    let prompt = session.prompt();
+
+    if keys.any_just_pressed([
+        KeyCode::Return,
+        KeyCode::Escape,
+        KeyCode::Up,
+        KeyCode::Down,
+        KeyCode::Left,
+        KeyCode::Right,
+    ]) {
+        let line = session.history.finish_search();
+        session.editor.set(&line);
+        shell_data.replace_line(&format!("{prompt}{line}"));
+        return;
+    }
+
+    if control && keys.just_pressed(KeyCode::G) {
+        let line = session.history.cancel_search();
+        session.editor.set(&line);
+        shell_data.replace_line(&format!("{prompt}{line}"));
+        return;
+    }
+
+    if control && keys.just_pressed(KeyCode::R) {
+        session.history.search_older();
+    } else if keys.just_pressed(KeyCode::Back) {
+        session.history.search_pop();
+    }
+
+    for event in event_reader.iter() {
+        if !event.char.is_control() {
+            session.history.search_push(event.char);
+        }
+    }
+
+    shell_data.replace_line(&session.history.search_line().unwrap_or_default());
+}
+
+fn execute(
+    registry: &CommandRegistry,
+    shell_data: &mut ShellData,
+    session: &mut ShellSession,
+    host: &mut Hosts,
+) {
+    let line = session.editor.take();
+    let output = session.execute(registry, host, &line);
+
+    if output.changes.contains(&StateChange::Cleared) {
+        shell_data.clear();
+    }
+
+    shell_data.print(&output);
+
+    shell_data.push_str(&session.prompt());
+}
+
+pub fn deselect(mut commands: Commands, shells: Query<Entity, With<Selected>>) {
+    for entity in shells.iter() {
+        commands.entity(entity).remove::<Selected>();
+    }
+}
+
+pub fn inspect_evidence(
+    shells: Query<(&Hostname, &ShellData, &ShellSession, &Host), With<Selected>>,
+    mut evidence_events: EventWriter<EvidenceEvent>,
+) {
+    for (hostname, shell_data, session, host) in shells.iter() {
+        let mut evidence = session.evidence(host);
+
+        if !shell_data.is_clear() {
+            evidence.push(Evidence::VisibleScreen);
+        }
+
+        if !evidence.is_empty() {
+            evidence_events.send(EvidenceEvent {
+                hostname: hostname.to_string(),
+                evidence,
+            });
         }
     }
 }
@@ -0,0 +1,303 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EditMode {
+    #[default]
+    Insert,
+    Normal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditAction {
+    HistoryOlder,
+    HistoryNewer,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LineEditor {
+    buffer: Vec<char>,
+    cursor: usize,
+    pub mode: EditMode,
+    pub vi: bool,
+    pending: Option<char>,
+}
+
+impl LineEditor {
+    pub fn line(&self) -> String {
+        self.buffer.iter().collect()
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn previous(&self) -> Option<char> {
+        self.buffer.get(self.cursor.checked_sub(1)?).copied()
+    }
+
+    pub fn set(&mut self, line: &str) {
+        self.buffer = line.chars().collect();
+        self.cursor = self.buffer.len();
+    }
+
+    pub fn take(&mut self) -> String {
+        let line = self.line();
+
+        self.set("");
+        self.mode = EditMode::Insert;
+        self.pending = None;
+
+        line
+    }
+
+    pub fn insert(&mut self, character: char) {
+        self.buffer.insert(self.cursor, character);
+        self.cursor += 1;
+    }
+
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.buffer.remove(self.cursor);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.cursor < self.buffer.len() {
+            self.buffer.remove(self.cursor);
+        }
+    }
+
+    pub fn left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.buffer.len());
+    }
+
+    pub fn home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.cursor = self.buffer.len();
+    }
+
+    pub fn kill_before(&mut self) {
+        self.buffer.drain(..self.cursor);
+        self.cursor = 0;
+    }
+
+    pub fn kill_after(&mut self) {
+        self.buffer.truncate(self.cursor);
+    }
+
+    pub fn kill_word(&mut self) {
+        let start = self.word_start();
+
+        self.buffer.drain(start..self.cursor);
+        self.cursor = start;
+    }
+
+    pub fn word_backward(&mut self) {
+        self.cursor = self.word_start();
+    }
+
+    pub fn word_forward(&mut self) {
+        let length = self.buffer.len();
+
+        while self.cursor < length && !self.buffer[self.cursor].is_whitespace() {
+            self.cursor += 1;
+        }
+
+        while self.cursor < length && self.buffer[self.cursor].is_whitespace() {
+            self.cursor += 1;
+        }
+    }
+
+    fn word_start(&self) -> usize {
+        let mut start = self.cursor;
+
+        while start > 0 && self.buffer[start - 1].is_whitespace() {
+            start -= 1;
+        }
+
+        while start > 0 && !self.buffer[start - 1].is_whitespace() {
+            start -= 1;
+        }
+
+        start
+    }
+
+    pub fn escape(&mut self) -> bool {
+        if !self.vi || self.mode == EditMode::Normal {
+            return false;
+        }
+
+        self.mode = EditMode::Normal;
+        self.left();
+
+        true
+    }
+
+    pub fn normal(&mut self, command: char) -> Option<EditAction> {
+        if let Some(pending) = self.pending.take() {
+            if (pending, command) == ('d', 'd') {
+                self.set("");
+            }
+
+            return None;
+        }
+
+        match command {
+            'h' => self.left(),
+            'l' if self.cursor + 1 < self.buffer.len() => self.right(),
+            'j' => return Some(EditAction::HistoryNewer),
+            'k' => return Some(EditAction::HistoryOlder),
+            'w' => self.word_forward(),
+            'b' => self.word_backward(),
+            '0' => self.home(),
+            '$' => self.cursor = self.buffer.len().saturating_sub(1),
+            'x' => self.delete(),
+            'D' => self.kill_after(),
+            'd' => self.pending = Some('d'),
+            'i' => self.mode = EditMode::Insert,
+            'a' => {
+                self.right();
+                self.mode = EditMode::Insert;
+            }
+            'I' => {
+                self.home();
+                self.mode = EditMode::Insert;
+            }
+            'A' => {
+                self.end();
+                self.mode = EditMode::Insert;
+            }
+            _ => (),
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor(line: &str) -> LineEditor {
+        let mut editor = LineEditor::default();
+
+        editor.set(line);
+
+        editor
+    }
+
+    #[test]
+    fn insert_at_the_cursor() {
+        let mut editor = editor("ls /tmp");
+
+        editor.home();
+        editor.word_forward();
+        editor.insert('-');
+        editor.insert('l');
+        editor.insert(' ');
+
+        assert_eq!(editor.line(), "ls -l /tmp");
+        assert_eq!(editor.previous(), Some(' '));
+    }
+
+    #[test]
+    fn backspace_and_delete() {
+        let mut editor = editor("cat");
+
+        editor.left();
+        editor.backspace();
+        editor.delete();
+
+        assert_eq!(editor.line(), "c");
+        assert_eq!(editor.cursor(), 1);
+
+        editor.home();
+        editor.backspace();
+
+        assert_eq!(editor.line(), "c");
+        assert_eq!(editor.previous(), None);
+    }
+
+    #[test]
+    fn kill_commands() {
+        let mut editor = editor("echo hello world");
+
+        editor.kill_word();
+
+        assert_eq!(editor.line(), "echo hello ");
+
+        editor.word_backward();
+        editor.kill_after();
+
+        assert_eq!(editor.line(), "echo ");
+
+        editor.left();
+        editor.kill_before();
+
+        assert_eq!(editor.line(), " ");
+        assert_eq!(editor.cursor(), 0);
+    }
+
+    #[test]
+    fn take_resets_the_editor() {
+        let mut editor = editor("ls");
+
+        editor.vi = true;
+        editor.escape();
+
+        assert_eq!(editor.take(), "ls");
+        assert_eq!(editor.line(), "");
+        assert_eq!(editor.mode, EditMode::Insert);
+    }
+
+    #[test]
+    fn escape_needs_vi_mode() {
+        let mut editor = editor("ls");
+
+        assert!(!editor.escape());
+
+        editor.vi = true;
+
+        assert!(editor.escape());
+        assert!(!editor.escape());
+        assert_eq!(editor.mode, EditMode::Normal);
+        assert_eq!(editor.cursor(), 1);
+    }
+
+    #[test]
+    fn normal_mode_commands() {
+        let mut editor = editor("rm -rf /tmp");
+
+        editor.vi = true;
+        editor.escape();
+
+        assert_eq!(editor.normal('k'), Some(EditAction::HistoryOlder));
+        assert_eq!(editor.normal('j'), Some(EditAction::HistoryNewer));
+
+        editor.normal('0');
+        editor.normal('w');
+        editor.normal('D');
+
+        assert_eq!(editor.line(), "rm ");
+
+        editor.normal('0');
+        editor.normal('x');
+
+        assert_eq!(editor.line(), "m ");
+
+        editor.normal('d');
+        editor.normal('d');
+
+        assert_eq!(editor.line(), "");
+
+        editor.normal('A');
+
+        assert_eq!(editor.mode, EditMode::Insert);
+    }
+}
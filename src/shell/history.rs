@@ -0,0 +1,234 @@
+use std::collections::VecDeque;
+
+use super::SHELL_HISTORY;
+
+#[derive(Clone, Debug, Default)]
+pub struct HistorySearch {
+    pub query: String,
+    matched: Option<usize>,
+    draft: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CommandHistory {
+    entries: VecDeque<String>,
+    position: Option<usize>,
+    draft: String,
+    search: Option<HistorySearch>,
+}
+
+impl CommandHistory {
+    pub fn push(&mut self, line: &str) {
+        self.position = None;
+        self.draft.clear();
+
+        let line = line.trim();
+
+        if line.is_empty() || self.entries.back().map(String::as_str) == Some(line) {
+            return;
+        }
+
+        self.entries.push_back(line.to_string());
+
+        while self.entries.len() > SHELL_HISTORY {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &String> {
+        self.entries.iter()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn older(&mut self, draft: &str) -> Option<&str> {
+        let position = match self.position {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = draft.to_string();
+                self.entries.len() - 1
+            }
+            Some(position) => position.saturating_sub(1),
+        };
+
+        self.position = Some(position);
+        self.entries.get(position).map(String::as_str)
+    }
+
+    pub fn newer(&mut self) -> Option<&str> {
+        let position = self.position? + 1;
+
+        if position >= self.entries.len() {
+            self.position = None;
+            return Some(&self.draft);
+        }
+
+        self.position = Some(position);
+        self.entries.get(position).map(String::as_str)
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.search.is_some()
+    }
+
+    pub fn start_search(&mut self, draft: &str) {
+        self.search = Some(HistorySearch {
+            draft: draft.to_string(),
+            ..Default::default()
+        });
+    }
+
+    pub fn search_push(&mut self, character: char) {
+        if let Some(search) = &mut self.search {
+            search.query.push(character);
+        }
+
+        self.find(self.entries.len());
+    }
+
+    pub fn search_pop(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.query.pop();
+        }
+
+        self.find(self.entries.len());
+    }
+
+    pub fn search_older(&mut self) {
+        let before = match &self.search {
+            Some(HistorySearch {
+                matched: Some(matched),
+                ..
+            }) => *matched,
+            _ => self.entries.len(),
+        };
+
+        self.find(before);
+    }
+
+    fn find(&mut self, before: usize) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+
+        if search.query.is_empty() {
+            search.matched = None;
+            return;
+        }
+
+        if let Some(matched) = (0..before)
+            .rev()
+            .find(|&idx| self.entries[idx].contains(&search.query))
+        {
+            search.matched = Some(matched);
+        }
+    }
+
+    pub fn search_line(&self) -> Option<String> {
+        let search = self.search.as_ref()?;
+        let matched = search
+            .matched
+            .and_then(|matched| self.entries.get(matched))
+            .map(String::as_str)
+            .unwrap_or_default();
+
+        Some(format!("(reverse-i-search)'{}': {matched}", search.query))
+    }
+
+    pub fn finish_search(&mut self) -> String {
+        let Some(search) = self.search.take() else {
+            return String::new();
+        };
+
+        match search.matched.and_then(|matched| self.entries.get(matched)) {
+            Some(matched) => matched.clone(),
+            None => search.draft,
+        }
+    }
+
+    pub fn cancel_search(&mut self) -> String {
+        self.search
+            .take()
+            .map(|search| search.draft)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(lines: &[&str]) -> CommandHistory {
+        let mut history = CommandHistory::default();
+
+        for line in lines {
+            history.push(line);
+        }
+
+        history
+    }
+
+    #[test]
+    fn push_skips_blank_lines_and_repeats() {
+        let history = history(&["ls", "  ", "ls", "pwd", "ls"]);
+
+        assert_eq!(history.entries().collect::<Vec<_>>(), ["ls", "pwd", "ls"]);
+    }
+
+    #[test]
+    fn push_keeps_the_latest_entries() {
+        let mut history = CommandHistory::default();
+
+        for idx in 0..=SHELL_HISTORY {
+            history.push(&format!("echo {idx}"));
+        }
+
+        assert_eq!(history.entries().count(), SHELL_HISTORY);
+        assert_eq!(history.entries().next().map(String::as_str), Some("echo 1"));
+    }
+
+    #[test]
+    fn older_and_newer_return_to_the_draft() {
+        let mut history = history(&["ls", "pwd"]);
+
+        assert_eq!(history.older("draft"), Some("pwd"));
+        assert_eq!(history.older("pwd"), Some("ls"));
+        assert_eq!(history.older("ls"), Some("ls"));
+        assert_eq!(history.newer(), Some("pwd"));
+        assert_eq!(history.newer(), Some("draft"));
+        assert_eq!(history.newer(), None);
+    }
+
+    #[test]
+    fn search_finds_older_matches() {
+        let mut history = history(&["cat /etc/hostname", "ls /etc", "pwd"]);
+
+        history.start_search("draft");
+
+        for character in "etc".chars() {
+            history.search_push(character);
+        }
+
+        assert_eq!(
+            history.search_line().as_deref(),
+            Some("(reverse-i-search)'etc': ls /etc")
+        );
+
+        history.search_older();
+
+        assert_eq!(history.finish_search(), "cat /etc/hostname");
+        assert!(!history.is_searching());
+    }
+
+    #[test]
+    fn cancel_search_restores_the_draft() {
+        let mut history = history(&["ls"]);
+
+        history.start_search("draft");
+        history.search_push('l');
+
+        assert_eq!(history.cancel_search(), "draft");
+    }
+}
@@ -1,45 +1,228 @@
 use self::{
-    controller::input,
-    model::{Hostname, Selected, ShellModel},
+    command::CommandRegistry,
+    controller::{deselect, input, inspect_evidence},
+    model::{AccessLevel, EvidenceEvent, Hostname, ShellData, ShellModel},
+    session::ShellSession,
     view::{hide, show, show_shell, ShellScreen, ShellView},
 };
-use crate::game::model::GameState;
+use crate::{
+    game::model::GameState,
+    host::{
+        database::{Database, Migration, Step},
+        filesystem::Permissions,
+        model::Host,
+        network::{Network, Protocol},
+        process::{ProcessTable, Service},
+        release::{Failure, Release},
+        state::HostState,
+    },
+};
 use bevy::prelude::*;
 
+pub mod builtins;
+pub mod command;
 pub mod controller;
+pub mod editor;
+pub mod history;
 pub mod model;
+pub mod script;
+pub mod session;
+pub mod tokenizer;
 pub mod view;
 
 const SHELL_WIDTH: usize = 80;
 const SHELL_HEIGHT: usize = 24;
+const SHELL_SCROLLBACK: usize = 1000;
+const SHELL_HISTORY: usize = 100;
+const SHELL_SCRIPT_DEPTH: usize = 8;
 
 pub struct ShellPlugin;
 
 impl Plugin for ShellPlugin {
     fn build(&self, app: &mut App) {
-        app.add_startup_system(startup)
+        app.add_event::<EvidenceEvent>()
+            .init_resource::<CommandRegistry>()
+            .add_startup_system(startup)
             .add_system(input.in_set(OnUpdate(GameState::Shell)))
             .add_systems((
                 hide.in_schedule(OnExit(GameState::Shell)),
+                inspect_evidence.in_schedule(OnExit(GameState::Shell)),
+                deselect.in_schedule(OnExit(GameState::Shell)),
                 show.in_schedule(OnEnter(GameState::Shell)),
                 show_shell.in_set(OnUpdate(GameState::Shell)),
             ));
     }
 }
 
-fn startup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let id = instantiate_shell(&mut commands, &asset_server, "test01");
+fn startup(mut commands: Commands, asset_server: Res<AssetServer>, mut network: ResMut<Network>) {
+    network
+        .add_subnet("office", "10.0.1")
+        .add_subnet("datacenter", "10.0.2")
+        .add_subnet("production", "10.0.3")
+        .add_host("test01", "office", 11)
+        .add_host("test02", "datacenter", 21)
+        .add_host("prod01", "production", 31)
+        .allow("office", "datacenter", Protocol::Icmp)
+        .allow("office", "datacenter", Protocol::Ssh)
+        .allow("datacenter", "production", Protocol::Icmp)
+        .allow("datacenter", "production", Protocol::Ssh);
+
+    let mut host = Host::new("test01");
+    host.add_file(
+        "/etc/app/config.yml",
+        "database:\n  host: test02\n  user: app\n  password: hunter2",
+        Permissions::restricted(AccessLevel::Operator),
+    )
+    .expect("host files are valid");
+    host.add_file(
+        "/home/notes.txt",
+        "remember to take the backup before the release",
+        Permissions::default(),
+    )
+    .expect("host files are valid");
+    host.state = HostState {
+        version: String::from("2.3.1"),
+        uptime: 3. * 86400. + 4. * 3600.,
+        users: vec![String::from("deploy")],
+        processes: ProcessTable::new(vec![
+            Service::new("app")
+                .owner(AccessLevel::Operator)
+                .requires("test02:postgres")
+                .release(),
+            Service::new("nginx").requires("app"),
+        ]),
+        disk_used: 12.4,
+        disk_total: 40.,
+        releases: releases(),
+        ..default()
+    };
+
+    instantiate_shell(&mut commands, &asset_server, "test01", host);
+
+    let mut host = Host::new("test02");
+    host.add_file(
+        "/var/backups/production.sql",
+        "CREATE TABLE customers (id INTEGER, name TEXT);\nINSERT INTO customers VALUES (1, 'Alice');",
+        Permissions::restricted(AccessLevel::Dba),
+    )
+    .expect("host files are valid");
+    host.state = HostState {
+        version: String::from("2.3.1"),
+        uptime: 41. * 86400. + 17. * 3600.,
+        users: vec![String::from("dba"), String::from("backup")],
+        processes: ProcessTable::new(vec![
+            Service::new("postgres").owner(AccessLevel::Dba),
+            Service::new("cron"),
+        ]),
+        database: Some(database()),
+        disk_used: 187.2,
+        disk_total: 250.,
+        releases: releases(),
+        ..default()
+    };
+
+    instantiate_shell(&mut commands, &asset_server, "test02", host);
+
+    let mut host = Host::new("prod01");
+    host.state = HostState {
+        version: String::from("2.3.1"),
+        uptime: 112. * 86400. + 2. * 3600.,
+        processes: ProcessTable::new(vec![
+            Service::new("app")
+                .owner(AccessLevel::Operator)
+                .requires("test02:postgres")
+                .release(),
+            Service::new("nginx").requires("app"),
+        ]),
+        disk_used: 31.9,
+        disk_total: 80.,
+        releases: releases(),
+        ..default()
+    };
+
+    commands.spawn((Hostname(String::from("prod01")), host));
+}
+
+fn releases() -> Vec<Release> {
+    vec![
+        Release::new("2.3.1"),
+        Release::new("2.3.2"),
+        Release {
+            requires: Some(String::from("2.3.2")),
+            schema_version: Some(42),
+            ..Release::new("2.4.0")
+        },
+        Release {
+            requires: Some(String::from("2.4.0")),
+            schema_version: Some(42),
+            failure: Some(Failure::DataLoss),
+            ..Release::new("2.5.0-rc1")
+        },
+    ]
+}
+
+fn database() -> Database {
+    let mut database = Database::new(41);
 
-    commands.entity(id).insert(Selected);
+    database.tables.extend([
+        (String::from("customers"), 120_000),
+        (String::from("orders"), 480_000),
+        (String::from("invoices"), 95_000),
+    ]);
 
-    instantiate_shell(&mut commands, &asset_server, "test02");
+    database.migrations = vec![
+        Migration {
+            version: 42,
+            name: String::from("split customer accounts"),
+            up: vec![
+                Step::CreateTable(String::from("accounts")),
+                Step::CopyRows {
+                    from: String::from("customers"),
+                    to: String::from("accounts"),
+                },
+                Step::DropTable(String::from("customers")),
+            ],
+            down: vec![
+                Step::CreateTable(String::from("customers")),
+                Step::CopyRows {
+                    from: String::from("accounts"),
+                    to: String::from("customers"),
+                },
+                Step::DropTable(String::from("accounts")),
+            ],
+            ..default()
+        },
+        Migration {
+            version: 43,
+            name: String::from("archive old orders"),
+            up: vec![
+                Step::CreateTable(String::from("orders_archive")),
+                Step::CopyRows {
+                    from: String::from("orders"),
+                    to: String::from("orders_archive"),
+                },
+                Step::Truncate(String::from("orders")),
+            ],
+            down: vec![
+                Step::CopyRows {
+                    from: String::from("orders_archive"),
+                    to: String::from("orders"),
+                },
+                Step::DropTable(String::from("orders_archive")),
+            ],
+            fails_at: Some(2),
+        },
+    ];
+
+    database
 }
 
 fn instantiate_shell(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
     hostname: &str,
-) -> Entity {
+    host: Host,
+) {
     let node_bundle = NodeBundle {
         style: Style {
             size: Size::new(Val::Percent(100.), Val::Percent(100.)),
@@ -60,11 +243,18 @@ fn instantiate_shell(
         })
     }));
 
+    let session = ShellSession::new(hostname);
+    let mut shell_data = ShellData::default();
+
+    shell_data.push_str(&session.prompt());
+
     commands
         .spawn((
             ShellModel {
                 hostname: Hostname(String::from(hostname)),
-                ..default()
+                shell_data,
+                session,
+                host,
             },
             ShellView {
                 node_bundle,
@@ -73,35 +263,5 @@ fn instantiate_shell(
         ))
         .with_children(|parent| {
             parent.spawn((shell_text, ShellScreen));
-        })
-        .id()
-}
-
-trait PreviousIterator: Iterator {
-    fn previous(&mut self) -> Option<Self::Item>;
-
-    #[inline]
-    fn first(self) -> Option<Self::Item>
-    where
-        Self: Sized,
-    {
-        #[inline]
-        fn some<T>(_: Option<T>, x: T) -> Option<T> {
-            Some(x)
-        }
-
-        PreviousIterator::fold(self, None, some)
-    }
-
-    fn fold<B, F>(mut self, init: B, mut f: F) -> B
-    where
-        Self: Sized,
-        F: FnMut(B, Self::Item) -> B,
-    {
-        let mut accum = init;
-        while let Some(x) = self.previous() {
-            accum = f(accum, x);
-        }
-        accum
-    }
+        });
 }
@@ -1,29 +1,21 @@
-use std::ops::{Index, IndexMut};
+use std::collections::VecDeque;
 
 use bevy::prelude::*;
+use serde::Deserialize;
 
-use super::{PreviousIterator, SHELL_HEIGHT, SHELL_WIDTH};
+use crate::host::{forensics::Evidence, model::Host};
 
-const USER_LEVEL_COMMANDS: [(&str, &str); 3] = [
-    ("login", "(elevates permissions if access card is valid)"),
-    ("clear", "(clears the screen)"),
-    ("show", "<property>"),
-];
+use super::{
+    session::{ShellOutput, ShellSession},
+    SHELL_HEIGHT, SHELL_SCROLLBACK, SHELL_WIDTH,
+};
 
-const ADMIN_LEVEL_COMMANDS: [(&str, &str); 5] = [
-    ("update", "<version>"),
-    ("save", "<in-file> <out-file>"),
-    ("show", "<property>"),
-    ("clear", "(clears the screen)"),
-    ("logout", "(deelevates permissions)"),
-];
-
-#[derive(Bundle, Default)]
+#[derive(Bundle)]
 pub struct ShellModel {
     pub hostname: Hostname,
     pub shell_data: ShellData,
-    pub current_line: CurrentPosition,
-    pub access_level: AccessLevel,
+    pub session: ShellSession,
+    pub host: Host,
 }
 
 #[derive(Component, Default, Deref, DerefMut, Clone)]
@@ -32,295 +24,195 @@ pub struct Hostname(pub String);
 #[derive(Component, Default)]
 pub struct Selected;
 
-#[derive(Component, Deref, DerefMut)]
-pub struct ShellData(pub [[char; SHELL_WIDTH]; SHELL_HEIGHT]);
+#[derive(Debug)]
+pub struct EvidenceEvent {
+    pub hostname: String,
+    pub evidence: Vec<Evidence>,
+}
 
-impl ShellData {
-    fn push(&mut self, current_position: &mut CurrentPosition, character: char) {
-        self[*current_position] = character;
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Kind {
+    #[default]
+    Input,
+    Output,
+    Error,
+}
 
-        current_position.next();
-    }
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Entry {
+    pub kind: Kind,
+    pub text: String,
+}
 
-    fn push_str(&mut self, current_position: &mut CurrentPosition, string: &str) {
-        for character in string.chars() {
-            self.push(current_position, character);
+impl Entry {
+    pub fn new(kind: Kind, text: impl Into<String>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
         }
     }
+}
 
-    pub fn pop(&mut self, current_position: &mut CurrentPosition) -> char {
-        current_position.previous();
-
-        let removed_character = self[*current_position];
-        self[*current_position] = '\0';
+#[derive(Component)]
+pub struct ShellData {
+    lines: VecDeque<Entry>,
+    scroll: usize,
+}
 
-        removed_character
-    }
+impl ShellData {
+    fn current_line(&mut self) -> &mut Entry {
+        if self.lines.is_empty() {
+            self.lines.push_back(Entry::default());
+        }
 
-    pub fn clear(&mut self, current_position: &mut CurrentPosition) {
-        self.0 = [['\0'; SHELL_WIDTH]; SHELL_HEIGHT];
-        current_position.first();
+        self.lines.back_mut().expect("scrollback is never empty")
     }
 
-    pub fn insert(
-        event_reader: &mut EventReader<ReceivedCharacter>,
-        mut shell_data: Mut<ShellData>,
-        mut current_position: Mut<CurrentPosition>,
-        access_level: Mut<AccessLevel>,
-    ) {
-        for event in event_reader.iter() {
-            if event.char == '?' {
-                let line = shell_data.0[(*current_position).0]
-                    .iter()
-                    .collect::<String>();
-                shell_data.push_str(
-                    &mut current_position,
-                    &ShellCommands::describe(*access_level, &line),
-                );
-            } else {
-                shell_data.push(&mut current_position, event.char);
-            }
+    fn push(&mut self, character: char) {
+        if character == '\n' {
+            let kind = self.current_line().kind;
+            self.new_line(kind);
+        } else {
+            self.current_line().text.push(character);
         }
     }
-}
-
-impl Index<CurrentPosition> for ShellData {
-    type Output = char;
-
-    fn index(&self, index: CurrentPosition) -> &Self::Output {
-        &self.0[index.0][index.1]
-    }
-}
 
-impl IndexMut<CurrentPosition> for ShellData {
-    fn index_mut(&mut self, index: CurrentPosition) -> &mut Self::Output {
-        &mut self.0[index.0][index.1]
-    }
-}
+    pub fn push_str(&mut self, string: &str) {
+        self.scroll = 0;
 
-impl Default for ShellData {
-    fn default() -> Self {
-        Self([['\0'; SHELL_WIDTH]; SHELL_HEIGHT])
+        for character in string.chars() {
+            self.push(character);
+        }
     }
-}
 
-#[derive(Component, Default, Clone, Copy)]
-pub struct CurrentPosition(usize, usize);
+    pub fn new_line(&mut self, kind: Kind) {
+        self.lines.push_back(Entry {
+            kind,
+            text: String::new(),
+        });
 
-impl CurrentPosition {
-    fn new_line(&mut self) {
-        self.0 += 1;
-        self.1 = 0;
-        self.0 %= SHELL_HEIGHT;
+        while self.lines.len() > SHELL_SCROLLBACK {
+            self.lines.pop_front();
+        }
     }
-}
-
-impl Iterator for CurrentPosition {
-    type Item = Self;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.1 += 1;
-        self.0 += self.1 / SHELL_WIDTH;
-
-        if self.0 != SHELL_HEIGHT - 1 {
-            self.1 = self.1 % SHELL_WIDTH;
-        } else if self.1 == SHELL_WIDTH {
-            return None;
+    pub fn print(&mut self, output: &ShellOutput) {
+        for entry in &output.lines {
+            self.new_line(entry.kind);
+            self.push_str(&entry.text);
         }
 
-        Some(CurrentPosition(self.0, self.1))
+        self.new_line(Kind::Input);
     }
-}
 
-impl PreviousIterator for CurrentPosition {
-    fn previous(&mut self) -> Option<Self::Item> {
-        if self.1 == 0 {
-            if self.0 != 0 {
-                self.0 -= 1;
-            }
+    pub fn replace_line(&mut self, line: &str) {
+        self.scroll = 0;
+        self.current_line().text = line.to_string();
+    }
 
-            self.1 = SHELL_WIDTH - 1;
-        } else {
-            self.1 -= 1;
+    pub fn redraw(&mut self, line: &str) {
+        if self.lines.back().map(|entry| entry.text.as_str()) != Some(line) {
+            self.replace_line(line);
         }
+    }
 
-        if self.1 == 0 && self.0 == 0 {
-            return None;
-        }
+    pub fn is_clear(&self) -> bool {
+        self.lines.len() <= 1
+    }
 
-        Some(CurrentPosition(self.0, self.1))
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.scroll = 0;
     }
-}
 
-pub enum ShellProperty {}
+    pub fn scroll_up(&mut self, rows: usize) {
+        let hidden_rows = self.rows().len().saturating_sub(SHELL_HEIGHT);
 
-impl TryFrom<&str> for ShellProperty {
-    type Error = String;
+        self.scroll = (self.scroll + rows).min(hidden_rows);
+    }
 
-    fn try_from(_value: &str) -> Result<Self, Self::Error> {
-        Err("unknown property".to_string())
+    pub fn scroll_down(&mut self, rows: usize) {
+        self.scroll = self.scroll.saturating_sub(rows);
     }
-}
 
-pub enum ShellCommands {
-    Show(ShellProperty),
-    Login,
-    Logout,
-    Clear,
-    Update(String),
-    Save(String, String),
-    Empty,
-    Error(String),
-}
+    fn rows(&self) -> Vec<Entry> {
+        self.lines
+            .iter()
+            .flat_map(|line| {
+                let characters = line.text.chars().collect::<Vec<_>>();
+
+                if characters.is_empty() {
+                    return vec![Entry {
+                        kind: line.kind,
+                        text: String::new(),
+                    }];
+                }
 
-impl ShellCommands {
-    pub fn describe(access_level: AccessLevel, query: &str) -> String {
-        match access_level {
-            AccessLevel::User => format!(
-                "These are the available commands: \n- {}",
-                USER_LEVEL_COMMANDS
-                    .iter()
-                    .filter(|(command, _)| command.contains(query))
-                    .map(|(command, description)| format!("{command} {description}"))
-                    .collect::<Vec<_>>()
-                    .join("\n- ")
-            ),
-
-            AccessLevel::Admin => format!(
-                "These are the available commands: \n- {}",
-                ADMIN_LEVEL_COMMANDS
-                    .iter()
-                    .filter(|(command, _)| command.contains(query))
-                    .map(|(command, description)| format!("{command} {description}"))
-                    .collect::<Vec<_>>()
-                    .join("\n- ")
-            ),
-        }
+                characters
+                    .chunks(SHELL_WIDTH)
+                    .map(|row| Entry {
+                        kind: line.kind,
+                        text: row.iter().collect(),
+                    })
+                    .collect()
+            })
+            .collect()
     }
 
-    pub fn process(
-        access_level: &mut Mut<AccessLevel>,
-        shell_data: &mut Mut<ShellData>,
-        current_position: &mut Mut<CurrentPosition>,
-        hostname: Hostname,
-    ) {
-        let prompt = match **access_level {
-            AccessLevel::User => format!("{}> ", *hostname),
-            AccessLevel::Admin => format!("{}# ", *hostname),
-        };
+    pub fn viewport(&self) -> Vec<Entry> {
+        let rows = self.rows();
+        let end = rows.len().saturating_sub(self.scroll);
+        let start = end.saturating_sub(SHELL_HEIGHT);
 
-        let prompt_length = prompt.len();
+        rows[start..end].to_vec()
+    }
 
-        let line = shell_data.0[(**current_position).0]
-            .iter()
-            .enumerate()
-            .filter(|(idx, _)| idx >= &prompt_length)
-            .map(|(_, character)| character)
-            .collect::<String>();
-
-        let line = ShellCommands::from(line);
-
-        let result = match line {
-            ShellCommands::Show(_property) => format!("implement this please"),
-            ShellCommands::Login => {
-                if let AccessLevel::User = **access_level {
-                    **access_level = AccessLevel::Admin;
-                }
+    pub fn cursor(&self, column: usize) -> Option<(usize, usize)> {
+        if self.scroll > 0 {
+            return None;
+        }
 
-                format!("logged in")
-            }
-            ShellCommands::Logout => {
-                if let AccessLevel::Admin = **access_level {
-                    **access_level = AccessLevel::User;
-                    format!("logged out")
-                } else {
-                    format!("command not found")
-                }
-            }
-            ShellCommands::Clear => {
-                shell_data.clear(current_position);
-                format!("")
-            }
-            ShellCommands::Update(version) => format!("updated to version {version}"),
-            ShellCommands::Save(in_file, out_file) => {
-                if let AccessLevel::Admin = **access_level {
-                    format!("saved '{in_file}' to '{out_file}'")
-                } else {
-                    format!("command not found")
-                }
-            }
-            ShellCommands::Empty => format!(""),
-            ShellCommands::Error(error) => format!("error: {error}"),
-        };
+        let length = self.lines.back()?.text.chars().count();
+        let line_rows = length.max(1).div_ceil(SHELL_WIDTH);
+        let row = column / SHELL_WIDTH;
 
-        current_position.new_line();
-        shell_data.push_str(current_position, &result);
+        if row >= line_rows {
+            return None;
+        }
 
-        current_position.new_line();
-        shell_data.push_str(current_position, &format!("{}", prompt));
-    }
-}
+        let viewport_row = self.viewport().len().checked_sub(line_rows - row)?;
 
-impl From<String> for ShellCommands {
-    fn from(value: String) -> Self {
-        Self::from(value.as_ref())
+        Some((viewport_row, column % SHELL_WIDTH))
     }
 }
 
-impl From<&str> for ShellCommands {
-    fn from(command: &str) -> Self {
-        let command = command.split(" ").map(|s| s.trim()).collect::<Vec<_>>();
-
-        let first_command = match command.get(0) {
-            Some(&command) => command,
-            None => return Self::Empty,
-        };
-
-        match first_command {
-            "clear" => Self::Clear,
-            "login" => Self::Login,
-            "logout" => Self::Logout,
-            "show" => {
-                let property = match command.get(1) {
-                    Some(&property) => property,
-                    None => return Self::Error(String::from("not enough arguments, expected 1")),
-                };
-
-                match ShellProperty::try_from(property) {
-                    Ok(property) => Self::Show(property),
-                    Err(error) => Self::Error(error),
-                }
-            }
-            "update" => {
-                let version = match command.get(1) {
-                    Some(&property) => property,
-                    None => return Self::Error(String::from("not enough arguments, expected 1")),
-                };
-
-                Self::Update(version.to_string())
-            }
-            "save" => {
-                let in_file = match command.get(1) {
-                    Some(&file) => file,
-                    None => return Self::Error(String::from("not enough arguments, expected 2")),
-                };
-
-                let out_file = match command.get(1) {
-                    Some(&file) => file,
-                    None => return Self::Error(String::from("not enough arguments, expected 2")),
-                };
-
-                Self::Save(in_file.to_string(), out_file.to_string())
-            }
-            "" => Self::Empty,
-            _ => Self::Error(format!("unknown command '{first_command}'")),
+impl Default for ShellData {
+    fn default() -> Self {
+        Self {
+            lines: VecDeque::from([Entry::default()]),
+            scroll: 0,
         }
     }
 }
 
-#[derive(Component, Default, Clone, Copy)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
 pub enum AccessLevel {
+    Guest,
     #[default]
     User,
-    Admin,
+    Operator,
+    Dba,
+    Root,
+}
+
+impl std::fmt::Display for AccessLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccessLevel::Guest => write!(f, "guest"),
+            AccessLevel::User => write!(f, "user"),
+            AccessLevel::Operator => write!(f, "operator"),
+            AccessLevel::Dba => write!(f, "dba"),
+            AccessLevel::Root => write!(f, "root"),
+        }
+    }
 }
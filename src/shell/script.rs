@@ -0,0 +1,199 @@
+use super::tokenizer::{lex, Part, Token, TokenizeError};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SyntaxError {
+    Tokenize(TokenizeError),
+    UnexpectedToken(Token),
+    UnexpectedEnd,
+}
+
+impl std::fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyntaxError::Tokenize(error) => write!(f, "{error}"),
+            SyntaxError::UnexpectedToken(token) => write!(f, "syntax error near '{token}'"),
+            SyntaxError::UnexpectedEnd => write!(f, "syntax error: unexpected end of line"),
+        }
+    }
+}
+
+impl From<TokenizeError> for SyntaxError {
+    fn from(error: TokenizeError) -> Self {
+        SyntaxError::Tokenize(error)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Condition {
+    #[default]
+    Always,
+    Success,
+    Failure,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Redirect {
+    pub target: Vec<Part>,
+    pub append: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Invocation {
+    pub words: Vec<Vec<Part>>,
+    pub redirect: Option<Redirect>,
+}
+
+impl Invocation {
+    fn is_empty(&self) -> bool {
+        self.words.is_empty() && self.redirect.is_none()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pipeline {
+    pub condition: Condition,
+    pub commands: Vec<Invocation>,
+}
+
+pub fn parse(line: &str) -> Result<Vec<Pipeline>, SyntaxError> {
+    let mut pipelines = Vec::new();
+    let mut pipeline = Pipeline::default();
+    let mut invocation = Invocation::default();
+    let mut tokens = lex(line)?.into_iter();
+
+    while let Some(token) = tokens.next() {
+        let condition = match token {
+            Token::Word(word) => {
+                invocation.words.push(word);
+                continue;
+            }
+            Token::Redirect { append } => {
+                invocation.redirect = match tokens.next() {
+                    Some(Token::Word(target)) => Some(Redirect { target, append }),
+                    Some(token) => return Err(SyntaxError::UnexpectedToken(token)),
+                    None => return Err(SyntaxError::UnexpectedEnd),
+                };
+                continue;
+            }
+            _ if invocation.is_empty() => return Err(SyntaxError::UnexpectedToken(token)),
+            Token::Pipe => {
+                pipeline.commands.push(std::mem::take(&mut invocation));
+                continue;
+            }
+            Token::And => Condition::Success,
+            Token::Or => Condition::Failure,
+            Token::Semicolon => Condition::Always,
+        };
+
+        pipeline.commands.push(std::mem::take(&mut invocation));
+        pipelines.push(std::mem::replace(
+            &mut pipeline,
+            Pipeline {
+                condition,
+                commands: Vec::new(),
+            },
+        ));
+    }
+
+    if !invocation.is_empty() {
+        pipeline.commands.push(invocation);
+    } else if !pipeline.commands.is_empty() || pipeline.condition != Condition::Always {
+        return Err(SyntaxError::UnexpectedEnd);
+    }
+
+    if !pipeline.commands.is_empty() {
+        pipelines.push(pipeline);
+    }
+
+    Ok(pipelines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(literal: &str) -> Vec<Part> {
+        vec![Part::Literal(literal.to_string())]
+    }
+
+    fn invocation(words: &[&str]) -> Invocation {
+        Invocation {
+            words: words.iter().map(|literal| word(literal)).collect(),
+            redirect: None,
+        }
+    }
+
+    #[test]
+    fn pipelines_and_conditions() {
+        assert_eq!(
+            parse("cat log | grep error && echo found || echo none; ls"),
+            Ok(vec![
+                Pipeline {
+                    condition: Condition::Always,
+                    commands: vec![invocation(&["cat", "log"]), invocation(&["grep", "error"])],
+                },
+                Pipeline {
+                    condition: Condition::Success,
+                    commands: vec![invocation(&["echo", "found"])],
+                },
+                Pipeline {
+                    condition: Condition::Failure,
+                    commands: vec![invocation(&["echo", "none"])],
+                },
+                Pipeline {
+                    condition: Condition::Always,
+                    commands: vec![invocation(&["ls"])],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn redirects() {
+        assert_eq!(
+            parse("echo hi >> /tmp/out"),
+            Ok(vec![Pipeline {
+                condition: Condition::Always,
+                commands: vec![Invocation {
+                    redirect: Some(Redirect {
+                        target: word("/tmp/out"),
+                        append: true,
+                    }),
+                    ..invocation(&["echo", "hi"])
+                }],
+            }])
+        );
+    }
+
+    #[test]
+    fn trailing_semicolon_and_empty_lines() {
+        assert_eq!(parse("ls;").map(|pipelines| pipelines.len()), Ok(1));
+        assert_eq!(parse(""), Ok(vec![]));
+        assert_eq!(parse("# only a comment"), Ok(vec![]));
+    }
+
+    #[test]
+    fn syntax_errors() {
+        assert_eq!(
+            parse("| ls"),
+            Err(SyntaxError::UnexpectedToken(Token::Pipe))
+        );
+        assert_eq!(
+            parse("ls && && ls"),
+            Err(SyntaxError::UnexpectedToken(Token::And))
+        );
+        assert_eq!(parse("ls |"), Err(SyntaxError::UnexpectedEnd));
+        assert_eq!(parse("ls &&"), Err(SyntaxError::UnexpectedEnd));
+        assert_eq!(parse("echo >"), Err(SyntaxError::UnexpectedEnd));
+        assert_eq!(
+            parse("echo > | ls"),
+            Err(SyntaxError::UnexpectedToken(Token::Pipe))
+        );
+        assert_eq!(
+            parse("echo 'open"),
+            Err(SyntaxError::Tokenize(TokenizeError::UnterminatedQuote(
+                '\''
+            )))
+        );
+    }
+}
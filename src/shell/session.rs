@@ -0,0 +1,1063 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+
+use super::{
+    command::{is_flag, Argument, Arguments, CommandRegistry},
+    editor::LineEditor,
+    history::CommandHistory,
+    model::{AccessLevel, Entry, Kind},
+    script::{parse, Condition, Invocation, Pipeline},
+    tokenizer::{last_command, tokenize, Part},
+    SHELL_SCRIPT_DEPTH,
+};
+use crate::{
+    host::{filesystem::FileSystemError, forensics::Evidence, model::Host, network::Hosts},
+    item::model::AccessCard,
+};
+
+#[derive(Component, Clone, Debug)]
+pub struct ShellSession {
+    pub hostname: String,
+    pub access_level: AccessLevel,
+    pub cwd: String,
+    pub history: CommandHistory,
+    pub editor: LineEditor,
+    pub variables: BTreeMap<String, String>,
+    pub card: Option<AccessCard>,
+    pub hops: Vec<Hop>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Hop {
+    pub hostname: String,
+    pub access_level: AccessLevel,
+    pub cwd: String,
+}
+
+impl Hop {
+    // Every connection starts like a fresh login, as a plain user in `/`
+    pub fn remote(hostname: &str) -> Self {
+        Self {
+            hostname: hostname.to_string(),
+            access_level: AccessLevel::default(),
+            cwd: String::from("/"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StateChange {
+    Cleared,
+    AccessLevel(AccessLevel),
+    Script(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShellOutput {
+    pub kind: Kind,
+    pub lines: Vec<Entry>,
+    pub changes: Vec<StateChange>,
+}
+
+impl Default for ShellOutput {
+    fn default() -> Self {
+        Self {
+            kind: Kind::Output,
+            lines: Vec::new(),
+            changes: Vec::new(),
+        }
+    }
+}
+
+impl ShellOutput {
+    pub fn line(line: impl Into<String>) -> Self {
+        Self::lines(vec![line.into()])
+    }
+
+    pub fn lines(lines: Vec<String>) -> Self {
+        Self {
+            lines: lines
+                .into_iter()
+                .map(|line| Entry::new(Kind::Output, line))
+                .collect(),
+            ..default()
+        }
+    }
+
+    pub fn errors(lines: Vec<String>) -> Self {
+        Self {
+            kind: Kind::Error,
+            lines: lines
+                .into_iter()
+                .map(|line| Entry::new(Kind::Error, line))
+                .collect(),
+            ..default()
+        }
+    }
+
+    pub fn error(error: impl std::fmt::Display) -> Self {
+        Self::errors(vec![format!("error: {error}")])
+    }
+
+    pub fn result(result: Result<(), FileSystemError>) -> Self {
+        match result {
+            Ok(()) => Self::default(),
+            Err(error) => Self::error(error),
+        }
+    }
+
+    pub fn change(mut self, change: StateChange) -> Self {
+        self.changes.push(change);
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Completion {
+    pub line: String,
+    pub candidates: Vec<String>,
+}
+
+impl ShellSession {
+    pub fn new(hostname: &str) -> Self {
+        Self {
+            hostname: String::from(hostname),
+            access_level: AccessLevel::default(),
+            cwd: String::from("/"),
+            history: CommandHistory::default(),
+            editor: LineEditor::default(),
+            variables: BTreeMap::new(),
+            card: None,
+            hops: Vec::new(),
+        }
+    }
+
+    pub fn prompt(&self) -> String {
+        match self.access_level {
+            AccessLevel::Root => format!("{}# ", self.hostname),
+            AccessLevel::Guest | AccessLevel::User => format!("{}> ", self.hostname),
+            access_level => format!("{}({access_level})> ", self.hostname),
+        }
+    }
+
+    pub fn help(&self, registry: &CommandRegistry, host: &mut Hosts, line: &str) -> ShellOutput {
+        let line = last_command(line);
+        let words = match tokenize(line) {
+            Ok(words) => words,
+            Err(error) => return ShellOutput::error(error),
+        };
+
+        let name = match words.as_slice() {
+            [] => "",
+            [name] if !line.ends_with(char::is_whitespace) => name,
+            [name, ..] => {
+                let Some(command) = registry
+                    .get(name)
+                    .filter(|command| self.access_level >= command.access_level())
+                else {
+                    return ShellOutput::error(format!("unknown command '{name}'"));
+                };
+
+                let mut lines = vec![format!("usage: {}", command.usage())];
+
+                if let Some(completion) = self.complete(registry, host, line) {
+                    lines.push(completion.candidates.join("  "));
+                }
+
+                return ShellOutput::lines(lines);
+            }
+        };
+
+        match registry.describe(self.access_level, name) {
+            usages if usages.is_empty() => {
+                ShellOutput::error(format!("no command starts with '{name}'"))
+            }
+            usages => ShellOutput::lines(usages),
+        }
+    }
+
+    pub fn complete(
+        &self,
+        registry: &CommandRegistry,
+        host: &mut Hosts,
+        line: &str,
+    ) -> Option<Completion> {
+        host.select(&self.hostname).ok()?;
+
+        let (head, line) = line.split_at(line.len() - last_command(line).len());
+        let mut words = tokenize(line).ok()?;
+
+        let partial = match line.is_empty() || line.ends_with(char::is_whitespace) {
+            true => String::new(),
+            false => words.pop()?,
+        };
+
+        let mut candidates = match words.split_first() {
+            None => registry
+                .available(self.access_level)
+                .map(|command| command.name().to_string())
+                .collect(),
+            Some((name, arguments)) => {
+                let command = registry
+                    .get(name)
+                    .filter(|command| self.access_level >= command.access_level())?;
+
+                if partial.starts_with('-') {
+                    command
+                        .arguments()
+                        .iter()
+                        .filter_map(|argument| match argument {
+                            Argument::Flag(flag, access_level)
+                                if self.access_level >= *access_level =>
+                            {
+                                Some(flag.to_string())
+                            }
+                            _ => None,
+                        })
+                        .collect()
+                } else {
+                    let position = arguments
+                        .iter()
+                        .filter(|argument| !is_flag(command, argument))
+                        .count();
+
+                    command.complete(self, host, position, &partial)
+                }
+            }
+        };
+
+        candidates.retain(|candidate| candidate.starts_with(&partial));
+        candidates.sort();
+        candidates.dedup();
+
+        let completed = match candidates.as_slice() {
+            [] => return None,
+            [candidate] if candidate.ends_with(['/', ':']) => candidate.clone(),
+            [candidate] => format!("{candidate} "),
+            [first, rest @ ..] => rest.iter().fold(first.clone(), |prefix, candidate| {
+                prefix
+                    .chars()
+                    .zip(candidate.chars())
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a)
+                    .collect()
+            }),
+        };
+
+        let stem = line.strip_suffix(partial.as_str())?;
+        let directory = partial.rfind('/').map_or(0, |idx| idx + 1);
+
+        Some(Completion {
+            line: format!("{head}{stem}{completed}"),
+            candidates: candidates
+                .iter()
+                .map(|candidate| candidate[directory..].to_string())
+                .collect(),
+        })
+    }
+
+    pub fn execute(
+        &mut self,
+        registry: &CommandRegistry,
+        host: &mut Hosts,
+        line: &str,
+    ) -> ShellOutput {
+        if line.trim().is_empty() {
+            return ShellOutput::default();
+        }
+
+        if let Err(error) = host.select(&self.hostname) {
+            return ShellOutput::error(error);
+        }
+
+        let hostname = self.hostname.clone();
+        let access_level = self.access_level;
+        // Like `HISTCONTROL=ignorespace`, a leading space keeps a command out of the history
+        let hidden = line.starts_with(' ');
+
+        host.record(access_level, line.trim());
+
+        if !hidden {
+            self.history.push(line);
+            host.append_history(access_level, line.trim());
+        }
+
+        let output = self.run(registry, host, line, 0);
+
+        // A line that leaves the session at a lower level, like `logout`, is recorded at that level
+        if self.hostname == hostname
+            && self.access_level < access_level
+            && host.select(&hostname).is_ok()
+        {
+            host.relevel(line.trim(), access_level, self.access_level);
+
+            if !hidden {
+                host.move_history(line.trim(), access_level, self.access_level);
+            }
+        }
+
+        output
+    }
+
+    fn run(
+        &mut self,
+        registry: &CommandRegistry,
+        host: &mut Hosts,
+        line: &str,
+        depth: usize,
+    ) -> ShellOutput {
+        if depth > SHELL_SCRIPT_DEPTH {
+            return ShellOutput::error("sh: scripts nested too deeply");
+        }
+
+        let pipelines = match parse(line) {
+            Ok(pipelines) => pipelines,
+            Err(error) => return ShellOutput::error(error),
+        };
+
+        let mut output = ShellOutput::default();
+
+        for pipeline in pipelines {
+            let success = output.kind != Kind::Error;
+
+            match pipeline.condition {
+                Condition::Success if !success => continue,
+                Condition::Failure if success => continue,
+                _ => (),
+            }
+
+            // `ssh` and `exit` move the session, so every pipeline runs on the host it is on now
+            let result = match host.select(&self.hostname) {
+                Ok(()) => self.pipeline(registry, host, &pipeline, depth),
+                Err(error) => ShellOutput::error(error),
+            };
+
+            if result.changes.contains(&StateChange::Cleared) {
+                output.lines.clear();
+            }
+
+            let status = match result.kind {
+                Kind::Error => "1",
+                _ => "0",
+            };
+
+            self.variables
+                .insert(String::from("?"), String::from(status));
+            output.kind = result.kind;
+            output.lines.extend(result.lines);
+            output.changes.extend(result.changes);
+        }
+
+        output
+    }
+
+    fn pipeline(
+        &mut self,
+        registry: &CommandRegistry,
+        host: &mut Hosts,
+        pipeline: &Pipeline,
+        depth: usize,
+    ) -> ShellOutput {
+        let mut stdin = None;
+        let mut changes = Vec::new();
+
+        for (idx, invocation) in pipeline.commands.iter().enumerate() {
+            let mut output = self.invoke(registry, host, invocation, stdin.take(), depth);
+
+            changes.append(&mut output.changes);
+
+            if output.kind == Kind::Error || idx + 1 == pipeline.commands.len() {
+                return ShellOutput { changes, ..output };
+            }
+
+            stdin = Some(output.lines.into_iter().map(|entry| entry.text).collect());
+        }
+
+        ShellOutput::default()
+    }
+
+    fn invoke(
+        &mut self,
+        registry: &CommandRegistry,
+        host: &mut Hosts,
+        invocation: &Invocation,
+        stdin: Option<Vec<String>>,
+        depth: usize,
+    ) -> ShellOutput {
+        let mut words = Vec::new();
+        let mut environment = Vec::new();
+
+        for word in &invocation.words {
+            let word = self.expand(word);
+
+            match assignment(&word).filter(|_| words.is_empty()) {
+                Some((name, value)) => {
+                    let previous = self.variables.insert(name.to_string(), value.to_string());
+
+                    environment.push((name.to_string(), previous));
+                }
+                None => words.push(word),
+            }
+        }
+
+        let has_command = !words.is_empty();
+        let mut output = match has_command {
+            true => self.command(registry, host, words, stdin),
+            false => ShellOutput::default(),
+        };
+
+        if let Some(StateChange::Script(script)) = output
+            .changes
+            .iter()
+            .position(|change| matches!(change, StateChange::Script(_)))
+            .map(|idx| output.changes.remove(idx))
+        {
+            output = script.lines().filter(|line| !line.trim().is_empty()).fold(
+                ShellOutput::default(),
+                |mut output, line| {
+                    let result = self.run(registry, host, line, depth + 1);
+
+                    output.kind = result.kind;
+                    output.lines.extend(result.lines);
+                    output.changes.extend(result.changes);
+                    output
+                },
+            );
+        }
+
+        // Assignments in front of a command only last for that command, like `NAME=value command`
+        if has_command {
+            for (name, previous) in environment.into_iter().rev() {
+                match previous {
+                    Some(value) => self.variables.insert(name, value),
+                    None => self.variables.remove(&name),
+                };
+            }
+        }
+
+        let Some(redirect) = &invocation.redirect else {
+            return output;
+        };
+
+        if output.kind == Kind::Error {
+            return output;
+        }
+
+        let path = self.expand(&redirect.target);
+        let mut contents = match redirect.append {
+            true => match host.filesystem.read(&self.cwd, &path, self.access_level) {
+                Ok(contents) => contents.to_string(),
+                Err(FileSystemError::NotFound) => String::new(),
+                Err(error) => return ShellOutput::error(format!("{path}: {error}")),
+            },
+            false => String::new(),
+        };
+
+        let (written, shown): (Vec<_>, Vec<_>) = output
+            .lines
+            .into_iter()
+            .partition(|entry| entry.kind == Kind::Output);
+
+        for entry in written {
+            contents.push_str(&entry.text);
+            contents.push('\n');
+        }
+
+        match host
+            .filesystem
+            .write(&self.cwd, &path, &contents, self.access_level)
+        {
+            Ok(()) => ShellOutput {
+                lines: shown,
+                ..output
+            },
+            Err(error) => ShellOutput::error(format!("{path}: {error}")),
+        }
+    }
+
+    fn command(
+        &mut self,
+        registry: &CommandRegistry,
+        host: &mut Hosts,
+        words: Vec<String>,
+        stdin: Option<Vec<String>>,
+    ) -> ShellOutput {
+        let mut words = words.into_iter();
+
+        let Some(name) = words.next() else {
+            return ShellOutput::default();
+        };
+
+        let Some(command) = registry.get(&name) else {
+            return ShellOutput::error(format!("unknown command '{name}'"));
+        };
+
+        let arguments = match Arguments::parse(command, words.collect()) {
+            Ok(arguments) => arguments.with_stdin(stdin),
+            Err(error) => {
+                return ShellOutput::errors(vec![
+                    format!("error: {error}"),
+                    format!("usage: {}", command.synopsis()),
+                ])
+            }
+        };
+
+        if !command.permitted(self.access_level, &arguments) {
+            return ShellOutput::error("permission denied");
+        }
+
+        command.execute(self, host, &arguments)
+    }
+
+    fn expand(&self, word: &[Part]) -> String {
+        word.iter()
+            .map(|part| match part {
+                Part::Literal(literal) => literal.clone(),
+                Part::Variable(name) => match name.as_str() {
+                    "HOSTNAME" => self.hostname.clone(),
+                    "PWD" => self.cwd.clone(),
+                    name => self.variables.get(name).cloned().unwrap_or_default(),
+                },
+            })
+            .collect()
+    }
+
+    pub fn evidence(&self, host: &Host) -> Vec<Evidence> {
+        let mut evidence = host.evidence();
+
+        if !self.hops.is_empty() {
+            evidence.push(Evidence::OpenConnection(self.hostname.clone()));
+        }
+
+        if self.access_level > AccessLevel::User {
+            evidence.push(Evidence::OpenSession(self.access_level));
+        }
+
+        evidence
+    }
+
+    pub fn connect(&mut self, hostname: &str) {
+        let remote = Hop::remote(hostname);
+
+        self.hops.push(Hop {
+            hostname: std::mem::replace(&mut self.hostname, remote.hostname),
+            access_level: std::mem::replace(&mut self.access_level, remote.access_level),
+            cwd: std::mem::replace(&mut self.cwd, remote.cwd),
+        });
+    }
+
+    pub fn disconnect(&mut self) -> Option<String> {
+        let hop = self.hops.pop()?;
+
+        self.access_level = hop.access_level;
+        self.cwd = hop.cwd;
+
+        Some(std::mem::replace(&mut self.hostname, hop.hostname))
+    }
+
+    pub fn set_access_level(&mut self, access_level: AccessLevel, message: &str) -> ShellOutput {
+        self.access_level = access_level;
+
+        ShellOutput::line(message).change(StateChange::AccessLevel(access_level))
+    }
+}
+
+fn assignment(word: &str) -> Option<(&str, &str)> {
+    let (name, value) = word.split_once('=')?;
+    let valid = name.chars().enumerate().all(|(idx, character)| {
+        character == '_' || character.is_ascii_alphabetic() || idx > 0 && character.is_ascii_digit()
+    });
+
+    (!name.is_empty() && valid).then_some((name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host::{
+        filesystem::Permissions,
+        network::{Network, Protocol},
+    };
+
+    fn network() -> Network {
+        let mut network = Network::default();
+
+        network
+            .add_subnet("office", "10.0.1")
+            .add_subnet("datacenter", "10.0.2")
+            .add_host("test01", "office", 11)
+            .add_host("test02", "datacenter", 21)
+            .allow("office", "datacenter", Protocol::Ssh);
+
+        network
+    }
+
+    fn execute(
+        session: &mut ShellSession,
+        test01: &mut Host,
+        test02: &mut Host,
+        line: &str,
+    ) -> ShellOutput {
+        let network = network();
+        let mut hosts = Hosts::new(
+            &network,
+            [
+                (String::from("test01"), test01),
+                (String::from("test02"), test02),
+            ],
+        );
+
+        session.execute(&CommandRegistry::default(), &mut hosts, line)
+    }
+
+    fn text(output: &ShellOutput) -> Vec<&str> {
+        output
+            .lines
+            .iter()
+            .map(|entry| entry.text.as_str())
+            .collect()
+    }
+
+    fn setup() -> (ShellSession, Host, Host) {
+        let mut test01 = Host::new("test01");
+
+        test01
+            .add_file(
+                "/home/words.txt",
+                "alpha\nbeta\nalphabet",
+                Permissions::default(),
+            )
+            .unwrap();
+
+        (ShellSession::new("test01"), test01, Host::new("test02"))
+    }
+
+    #[test]
+    fn commands_report_errors() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        let output = execute(&mut session, &mut test01, &mut test02, "cat /etc/hostname");
+
+        assert_eq!(output.kind, Kind::Output);
+        assert_eq!(text(&output), ["test01"]);
+
+        let output = execute(&mut session, &mut test01, &mut test02, "cat /nope");
+
+        assert_eq!(output.kind, Kind::Error);
+        assert_eq!(
+            output
+                .lines
+                .iter()
+                .map(|entry| entry.kind)
+                .collect::<Vec<_>>(),
+            [Kind::Error]
+        );
+        assert_eq!(
+            execute(&mut session, &mut test01, &mut test02, "nope").kind,
+            Kind::Error
+        );
+    }
+
+    #[test]
+    fn login_and_logout_change_the_access_level() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        assert_eq!(
+            text(&execute(&mut session, &mut test01, &mut test02, "login")),
+            ["error: login: no access card inserted"]
+        );
+
+        session.card = Some(AccessCard {
+            name: String::from("ops"),
+            clearance: AccessLevel::Root,
+            hosts: vec![String::from("test02")],
+        });
+
+        assert_eq!(
+            text(&execute(&mut session, &mut test01, &mut test02, "login")),
+            ["error: login: access card 'ops' rejected"]
+        );
+        assert_eq!(test01.failed_logins, 2);
+
+        session.card = Some(AccessCard {
+            hosts: Vec::new(),
+            ..session.card.unwrap()
+        });
+
+        assert_eq!(
+            execute(&mut session, &mut test01, &mut test02, "login"),
+            ShellOutput {
+                kind: Kind::Output,
+                lines: vec![Entry {
+                    kind: Kind::Output,
+                    text: String::from("logged in"),
+                }],
+                changes: vec![StateChange::AccessLevel(AccessLevel::Root)],
+            }
+        );
+        assert_eq!(session.prompt(), "test01# ");
+        assert_eq!(
+            text(&execute(&mut session, &mut test01, &mut test02, "logout")),
+            ["logged out"]
+        );
+        assert_eq!(session.access_level, AccessLevel::User);
+        assert_eq!(
+            text(&execute(&mut session, &mut test01, &mut test02, "logout")),
+            ["error: permission denied"]
+        );
+    }
+
+    #[test]
+    fn clear_is_reported_as_a_change() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        assert_eq!(
+            execute(&mut session, &mut test01, &mut test02, "clear").changes,
+            [StateChange::Cleared]
+        );
+    }
+
+    #[test]
+    fn errors_are_prefixed() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        assert_eq!(
+            text(&execute(&mut session, &mut test01, &mut test02, "cat")),
+            ["error: cat: missing argument <file>", "usage: cat <file>"]
+        );
+    }
+
+    #[test]
+    fn lines_are_recorded_unless_hidden() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        execute(&mut session, &mut test01, &mut test02, "ls /home");
+        execute(&mut session, &mut test01, &mut test02, " cat /etc/hostname");
+
+        assert_eq!(session.history.entries().collect::<Vec<_>>(), ["ls /home"]);
+        assert_eq!(
+            test01
+                .filesystem
+                .read("/", "/home/.history", AccessLevel::User),
+            Ok("ls /home\n")
+        );
+        assert_eq!(
+            test01
+                .audit
+                .iter()
+                .map(|entry| entry.command.as_str())
+                .collect::<Vec<_>>(),
+            ["ls /home", "cat /etc/hostname"]
+        );
+    }
+
+    #[test]
+    fn logout_is_recorded_at_the_lower_level() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        session.access_level = AccessLevel::Root;
+        execute(&mut session, &mut test01, &mut test02, "logout");
+
+        assert_eq!(session.access_level, AccessLevel::User);
+        assert_eq!(
+            test01.audit.last().map(|entry| entry.access_level),
+            Some(AccessLevel::User)
+        );
+        assert_eq!(
+            test01
+                .filesystem
+                .read("/", "/home/.history", AccessLevel::User),
+            Ok("logout\n")
+        );
+    }
+
+    #[test]
+    fn file_commands_use_the_working_directory() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        execute(&mut session, &mut test01, &mut test02, "cd /home");
+        execute(&mut session, &mut test01, &mut test02, "mkdir notes");
+        execute(
+            &mut session,
+            &mut test01,
+            &mut test02,
+            "cp /etc/hostname notes",
+        );
+
+        assert_eq!(
+            text(&execute(&mut session, &mut test01, &mut test02, "pwd")),
+            ["/home"]
+        );
+        assert_eq!(
+            text(&execute(&mut session, &mut test01, &mut test02, "ls notes")),
+            ["hostname"]
+        );
+        assert_eq!(
+            text(&execute(
+                &mut session,
+                &mut test01,
+                &mut test02,
+                "cat notes/hostname"
+            )),
+            ["test01"]
+        );
+        assert_eq!(
+            text(&execute(
+                &mut session,
+                &mut test01,
+                &mut test02,
+                "cat /root/secret"
+            )),
+            ["error: /root/secret: permission denied"]
+        );
+    }
+
+    #[test]
+    fn ssh_moves_the_session_between_hosts() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        execute(&mut session, &mut test01, &mut test02, "ssh test02");
+
+        assert_eq!(session.hostname, "test02");
+        assert_eq!(
+            text(&execute(
+                &mut session,
+                &mut test01,
+                &mut test02,
+                "cat /etc/hostname"
+            )),
+            ["test02"]
+        );
+
+        execute(&mut session, &mut test01, &mut test02, "exit");
+
+        assert_eq!(session.hostname, "test01");
+        assert_eq!(
+            text(&execute(&mut session, &mut test01, &mut test02, "exit")),
+            ["error: exit: not connected to a remote host"]
+        );
+    }
+
+    #[test]
+    fn pipes_and_conditions() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        let output = execute(
+            &mut session,
+            &mut test01,
+            &mut test02,
+            "cat /home/words.txt | grep alpha | grep bet",
+        );
+
+        assert_eq!(text(&output), ["alphabet"]);
+
+        let output = execute(
+            &mut session,
+            &mut test01,
+            &mut test02,
+            "cat /nope && echo no || echo recovered; echo $?",
+        );
+
+        assert_eq!(output.kind, Kind::Output);
+        assert_eq!(text(&output)[1..], ["recovered", "0"]);
+    }
+
+    #[test]
+    fn redirects_write_files() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        let output = execute(
+            &mut session,
+            &mut test01,
+            &mut test02,
+            "echo one > /tmp/out; echo two >> /tmp/out",
+        );
+
+        assert_eq!(text(&output), Vec::<&str>::new());
+        assert_eq!(
+            test01.filesystem.read("/", "/tmp/out", AccessLevel::User),
+            Ok("one\ntwo\n")
+        );
+    }
+
+    #[test]
+    fn appending_to_an_unreadable_file_fails() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        test01
+            .add_file(
+                "/home/notes",
+                "secret\n",
+                Permissions {
+                    read: AccessLevel::Root,
+                    write: AccessLevel::User,
+                },
+            )
+            .unwrap();
+
+        let output = execute(
+            &mut session,
+            &mut test01,
+            &mut test02,
+            "echo more >> /home/notes",
+        );
+
+        assert_eq!(text(&output), ["error: /home/notes: permission denied"]);
+        assert_eq!(
+            test01
+                .filesystem
+                .read("/", "/home/notes", AccessLevel::Root),
+            Ok("secret\n")
+        );
+    }
+
+    #[test]
+    fn variables_and_prefix_assignments() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        execute(&mut session, &mut test01, &mut test02, "NAME=world");
+
+        let output = execute(
+            &mut session,
+            &mut test01,
+            &mut test02,
+            "NAME=there echo \"hello $NAME\" '$NAME'",
+        );
+
+        assert_eq!(text(&output), ["hello there $NAME"]);
+
+        let output = execute(&mut session, &mut test01, &mut test02, "echo $NAME");
+
+        assert_eq!(text(&output), ["world"]);
+    }
+
+    #[test]
+    fn ssh_follows_the_firewall_and_starts_a_fresh_login() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        session.access_level = AccessLevel::Root;
+        session.cwd = String::from("/home");
+        execute(&mut session, &mut test01, &mut test02, "ssh 10.0.2.21");
+
+        assert_eq!(session.hostname, "test02");
+        assert_eq!(session.access_level, AccessLevel::User);
+        assert_eq!(session.cwd, "/");
+        assert_eq!(
+            text(&execute(
+                &mut session,
+                &mut test01,
+                &mut test02,
+                "ssh test01"
+            )),
+            ["error: ssh: connection to test01 timed out"]
+        );
+
+        execute(&mut session, &mut test01, &mut test02, "exit");
+
+        assert_eq!(session.access_level, AccessLevel::Root);
+        assert_eq!(session.cwd, "/home");
+    }
+
+    #[test]
+    fn scp_copies_between_hosts() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        let output = execute(
+            &mut session,
+            &mut test01,
+            &mut test02,
+            "scp /home/words.txt test02:/tmp",
+        );
+
+        assert_eq!(text(&output), Vec::<&str>::new());
+        assert_eq!(
+            test02
+                .filesystem
+                .read("/", "/tmp/words.txt", AccessLevel::User),
+            Ok("alpha\nbeta\nalphabet")
+        );
+
+        let output = execute(
+            &mut session,
+            &mut test01,
+            &mut test02,
+            "scp test02:/etc/hostname /home/copy",
+        );
+
+        assert_eq!(text(&output), Vec::<&str>::new());
+        assert_eq!(
+            test01.filesystem.read("/", "/home/copy", AccessLevel::User),
+            Ok("test02")
+        );
+    }
+
+    #[test]
+    fn scp_uses_the_remote_login_access_level() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        test01
+            .add_file(
+                "/root/notes",
+                "root only",
+                Permissions::restricted(AccessLevel::Root),
+            )
+            .unwrap();
+        session.access_level = AccessLevel::Root;
+
+        assert_eq!(
+            text(&execute(
+                &mut session,
+                &mut test01,
+                &mut test02,
+                "scp test02:/root/secret /home"
+            )),
+            ["error: scp: /root/secret: permission denied"]
+        );
+        assert_eq!(
+            text(&execute(
+                &mut session,
+                &mut test01,
+                &mut test02,
+                "scp /root/notes test02:/tmp"
+            )),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn chained_commands_follow_ssh() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        let output = execute(
+            &mut session,
+            &mut test01,
+            &mut test02,
+            "ssh test02; cat /etc/hostname; exit; cat /etc/hostname",
+        );
+
+        assert_eq!(
+            text(&output),
+            [
+                "connected to test02 (10.0.2.21)",
+                "test02",
+                "connection to test02 closed",
+                "test01",
+            ]
+        );
+    }
+
+    #[test]
+    fn scripts_cannot_recurse_forever() {
+        let (mut session, mut test01, mut test02) = setup();
+
+        test01
+            .add_file("/home/loop.sh", "sh /home/loop.sh", Permissions::default())
+            .unwrap();
+
+        let output = execute(&mut session, &mut test01, &mut test02, "sh /home/loop.sh");
+
+        assert_eq!(output.kind, Kind::Error);
+        assert_eq!(text(&output), ["error: sh: scripts nested too deeply"]);
+    }
+}
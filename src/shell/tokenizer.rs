@@ -0,0 +1,294 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenizeError {
+    UnterminatedQuote(char),
+    TrailingEscape,
+    UnterminatedVariable,
+}
+
+impl std::fmt::Display for TokenizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenizeError::UnterminatedQuote(quote) => write!(f, "unterminated quote {quote}"),
+            TokenizeError::TrailingEscape => write!(f, "trailing backslash"),
+            TokenizeError::UnterminatedVariable => write!(f, "unterminated ${{"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Part {
+    Literal(String),
+    Variable(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Token {
+    Word(Vec<Part>),
+    Pipe,
+    And,
+    Or,
+    Semicolon,
+    Redirect { append: bool },
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(parts) => {
+                for part in parts {
+                    match part {
+                        Part::Literal(literal) => write!(f, "{literal}")?,
+                        Part::Variable(name) => write!(f, "${name}")?,
+                    }
+                }
+
+                Ok(())
+            }
+            Token::Pipe => write!(f, "|"),
+            Token::And => write!(f, "&&"),
+            Token::Or => write!(f, "||"),
+            Token::Semicolon => write!(f, ";"),
+            Token::Redirect { append: false } => write!(f, ">"),
+            Token::Redirect { append: true } => write!(f, ">>"),
+        }
+    }
+}
+
+fn push_literal(word: &mut Option<Vec<Part>>, character: char) {
+    let parts = word.get_or_insert_with(Vec::new);
+
+    match parts.last_mut() {
+        Some(Part::Literal(literal)) => literal.push(character),
+        _ => parts.push(Part::Literal(character.to_string())),
+    }
+}
+
+fn push_variable(
+    word: &mut Option<Vec<Part>>,
+    characters: &mut std::iter::Peekable<std::str::Chars>,
+) -> Result<(), TokenizeError> {
+    let name = match characters.peek() {
+        Some('?') => characters.next().map(String::from),
+        Some('{') => {
+            characters.next();
+
+            let mut name = String::new();
+
+            loop {
+                match characters.next() {
+                    Some('}') => break,
+                    Some(character) => name.push(character),
+                    None => return Err(TokenizeError::UnterminatedVariable),
+                }
+            }
+
+            Some(name)
+        }
+        Some(character) if character.is_ascii_alphabetic() || *character == '_' => {
+            let mut name = String::new();
+
+            while let Some(character) = characters
+                .next_if(|character| character.is_ascii_alphanumeric() || *character == '_')
+            {
+                name.push(character);
+            }
+
+            Some(name)
+        }
+        _ => None,
+    };
+
+    match name {
+        Some(name) => word.get_or_insert_with(Vec::new).push(Part::Variable(name)),
+        None => push_literal(word, '$'),
+    }
+
+    Ok(())
+}
+
+pub fn lex(line: &str) -> Result<Vec<Token>, TokenizeError> {
+    let mut tokens = Vec::new();
+    let mut current: Option<Vec<Part>> = None;
+    let mut characters = line.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        let operator = match character {
+            '|' if characters.next_if_eq(&'|').is_some() => Some(Token::Or),
+            '|' => Some(Token::Pipe),
+            '&' if characters.next_if_eq(&'&').is_some() => Some(Token::And),
+            ';' => Some(Token::Semicolon),
+            '>' if characters.next_if_eq(&'>').is_some() => Some(Token::Redirect { append: true }),
+            '>' => Some(Token::Redirect { append: false }),
+            _ => None,
+        };
+
+        if let Some(operator) = operator {
+            tokens.extend(current.take().map(Token::Word));
+            tokens.push(operator);
+            continue;
+        }
+
+        match character {
+            character if character.is_whitespace() => {
+                tokens.extend(current.take().map(Token::Word));
+            }
+            '#' if current.is_none() => break,
+            '$' => push_variable(&mut current, &mut characters)?,
+            '\\' => {
+                let escaped = characters.next().ok_or(TokenizeError::TrailingEscape)?;
+                push_literal(&mut current, escaped);
+            }
+            '\'' => {
+                current.get_or_insert_with(Vec::new);
+
+                loop {
+                    match characters.next() {
+                        Some('\'') => break,
+                        Some(character) => push_literal(&mut current, character),
+                        None => return Err(TokenizeError::UnterminatedQuote('\'')),
+                    }
+                }
+            }
+            '"' => {
+                current.get_or_insert_with(Vec::new);
+
+                loop {
+                    match characters.next() {
+                        Some('"') => break,
+                        Some('$') => push_variable(&mut current, &mut characters)?,
+                        Some('\\') => match characters.next() {
+                            Some(escaped @ ('"' | '\\' | '$')) => {
+                                push_literal(&mut current, escaped)
+                            }
+                            Some(character) => {
+                                push_literal(&mut current, '\\');
+                                push_literal(&mut current, character);
+                            }
+                            None => return Err(TokenizeError::UnterminatedQuote('"')),
+                        },
+                        Some(character) => push_literal(&mut current, character),
+                        None => return Err(TokenizeError::UnterminatedQuote('"')),
+                    }
+                }
+            }
+            character => push_literal(&mut current, character),
+        }
+    }
+
+    tokens.extend(current.map(Token::Word));
+
+    Ok(tokens)
+}
+
+pub fn tokenize(line: &str) -> Result<Vec<String>, TokenizeError> {
+    Ok(lex(line)?.iter().map(Token::to_string).collect())
+}
+
+pub fn last_command(line: &str) -> &str {
+    let mut start = 0;
+    let mut quote = None;
+    let mut characters = line.char_indices().peekable();
+
+    while let Some((idx, character)) = characters.next() {
+        match (quote, character) {
+            (None, '\\') => {
+                characters.next();
+            }
+            (None, '\'' | '"') => quote = Some(character),
+            (Some(open), character) if open == character => quote = None,
+            // A lone `&` is part of a word, as in `lex`
+            (None, '&') if characters.next_if(|(_, next)| *next == '&').is_some() => {
+                start = idx + 2
+            }
+            (None, '|' | ';') => start = idx + 1,
+            _ => (),
+        }
+    }
+
+    line[start..].trim_start()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_and_operators() {
+        assert_eq!(
+            tokenize("cat a.txt | grep b && echo ok || echo no; ls > out >> log"),
+            Ok(vec![
+                "cat", "a.txt", "|", "grep", "b", "&&", "echo", "ok", "||", "echo", "no", ";",
+                "ls", ">", "out", ">>", "log",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect())
+        );
+    }
+
+    #[test]
+    fn quotes_and_escapes() {
+        assert_eq!(
+            tokenize(r#"echo 'a b' "c \"d\"" e\ f ''"#),
+            Ok(vec![
+                String::from("echo"),
+                String::from("a b"),
+                String::from("c \"d\""),
+                String::from("e f"),
+                String::new(),
+            ])
+        );
+    }
+
+    #[test]
+    fn variables() {
+        assert_eq!(
+            lex(r#"$HOME "${USER}!" '$NOT' $? \$ $"#),
+            Ok(vec![
+                Token::Word(vec![Part::Variable(String::from("HOME"))]),
+                Token::Word(vec![
+                    Part::Variable(String::from("USER")),
+                    Part::Literal(String::from("!")),
+                ]),
+                Token::Word(vec![Part::Literal(String::from("$NOT"))]),
+                Token::Word(vec![Part::Variable(String::from("?"))]),
+                Token::Word(vec![Part::Literal(String::from("$"))]),
+                Token::Word(vec![Part::Literal(String::from("$"))]),
+            ])
+        );
+    }
+
+    #[test]
+    fn comments() {
+        assert_eq!(tokenize("# nothing"), Ok(vec![]));
+        assert_eq!(
+            tokenize("echo a#b # c"),
+            Ok(vec![String::from("echo"), String::from("a#b")])
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            tokenize("echo 'open"),
+            Err(TokenizeError::UnterminatedQuote('\''))
+        );
+        assert_eq!(
+            tokenize("echo \"open"),
+            Err(TokenizeError::UnterminatedQuote('"'))
+        );
+        assert_eq!(tokenize("echo \\"), Err(TokenizeError::TrailingEscape));
+        assert_eq!(
+            tokenize("echo ${HOME"),
+            Err(TokenizeError::UnterminatedVariable)
+        );
+    }
+
+    #[test]
+    fn last_command_skips_quoted_operators() {
+        assert_eq!(last_command("ls | grep 'a|b' && ca"), "ca");
+        assert_eq!(last_command("echo \"a;b\""), "echo \"a;b\"");
+        assert_eq!(last_command("ls && ca"), "ca");
+        assert_eq!(last_command("echo a&b"), "echo a&b");
+    }
+}
@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 
-use super::model::{Selected, ShellData};
+use super::{
+    editor::EditMode,
+    model::{Kind, Selected, ShellData},
+    session::ShellSession,
+};
 
 #[derive(Bundle, Default)]
 pub struct ShellView {
@@ -11,30 +15,66 @@ pub struct ShellView {
 #[derive(Component, Default)]
 pub struct ShellScreen;
 
-pub fn hide(mut shell_screens: Query<&mut Visibility, With<ShellScreen>>) {
+pub fn hide(mut shell_screens: Query<&mut Visibility, With<ShellData>>) {
     for mut shell_screen in &mut shell_screens {
         *shell_screen = Visibility::Hidden;
     }
 }
 
-pub fn show(mut shell_screens: Query<&mut Visibility, With<ShellScreen>>) {
-    for mut shell_screen in &mut shell_screens {
-        *shell_screen = Visibility::Inherited;
+pub fn show(mut shell_screens: Query<(&mut Visibility, Option<&Selected>), With<ShellData>>) {
+    for (mut shell_screen, selected) in &mut shell_screens {
+        *shell_screen = match selected {
+            Some(_) => Visibility::Inherited,
+            None => Visibility::Hidden,
+        };
     }
 }
 
 pub fn show_shell(
+    time: Res<Time>,
     mut texts: Query<&mut Text, With<ShellScreen>>,
-    shell_data: Query<(&ShellData, Option<&Selected>)>,
+    shell_data: Query<(&ShellData, &ShellSession, &Children), With<Selected>>,
 ) {
-    for (shell_data, selected) in shell_data.iter() {
-        if let None = selected {
-            continue;
+    for (shell_data, session, children) in shell_data.iter() {
+        let mut viewport = shell_data.viewport();
+        let blink = time.elapsed_seconds() % 1. < 0.5;
+        let column = match session.history.is_searching() {
+            true => None,
+            false => Some(session.prompt().chars().count() + session.editor.cursor()),
+        };
+
+        if let Some((row, column)) = column.and_then(|column| shell_data.cursor(column)) {
+            let cursor = match session.editor.mode {
+                EditMode::Normal => '▒',
+                EditMode::Insert if blink => '█',
+                EditMode::Insert => ' ',
+            };
+            let mut characters = viewport[row].text.chars().collect::<Vec<_>>();
+
+            match characters.get_mut(column) {
+                Some(character) if cursor != ' ' => *character = cursor,
+                Some(_) => (),
+                None => characters.push(cursor),
+            }
+
+            viewport[row].text = characters.into_iter().collect();
         }
 
-        for mut text in texts.iter_mut() {
-            for (idx, row) in shell_data.0.iter().enumerate() {
-                text.sections[idx].value = format!("{}\n", row.iter().collect::<String>());
+        let mut texts = texts.iter_many_mut(children);
+
+        while let Some(mut text) = texts.fetch_next() {
+            for (idx, section) in text.sections.iter_mut().enumerate() {
+                let Some(row) = viewport.get(idx) else {
+                    section.value = String::new();
+                    continue;
+                };
+
+                section.value = format!("{}\n", row.text);
+                section.style.color = match row.kind {
+                    Kind::Input => Color::GREEN,
+                    Kind::Output => Color::rgb(0.6, 0.85, 0.6),
+                    Kind::Error => Color::rgb(1., 0.4, 0.3),
+                };
             }
         }
     }